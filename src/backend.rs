@@ -0,0 +1,145 @@
+use minifb::{Key, MouseButton, MouseMode, Window, WindowOptions};
+use crate::framebuffer::Framebuffer;
+use crate::terminal::TerminalBackend;
+
+// Todo lo que el juego necesita de una pantalla: mostrar el framebuffer y leer la entrada.
+pub trait Backend {
+    fn is_open(&self) -> bool;
+    fn is_key_down(&self, key: Key) -> bool;
+    fn get_mouse_pos(&self) -> Option<(f32, f32)>;
+    fn is_mouse_down(&self) -> bool; // Botón izquierdo
+    fn set_cursor_visibility(&mut self, visible: bool);
+    fn warp_mouse(&self, x: f32, y: f32) -> bool; // false si el backend no puede mover el cursor
+    fn update(&mut self, framebuffer: &Framebuffer);
+}
+
+// minifb no puede mover el cursor; en X11 se hace directo con Xlib sobre la misma ventana
+#[cfg(target_os = "linux")]
+struct PointerWarp {
+    xlib: x11_dl::xlib::Xlib,
+    display: *mut x11_dl::xlib::Display,
+    window: std::os::raw::c_ulong,
+}
+
+#[cfg(target_os = "linux")]
+impl PointerWarp {
+    fn new(window: &Window) -> Option<PointerWarp> {
+        use raw_window_handle::{HasDisplayHandle, HasWindowHandle, RawDisplayHandle, RawWindowHandle};
+
+        // En Wayland no hay forma de mover el cursor
+        let RawDisplayHandle::Xlib(display) = window.display_handle().ok()?.as_raw() else {
+            return None;
+        };
+        let RawWindowHandle::Xlib(handle) = window.window_handle().ok()?.as_raw() else {
+            return None;
+        };
+        let xlib = x11_dl::xlib::Xlib::open().ok()?;
+        Some(PointerWarp { xlib, display: display.display?.as_ptr().cast(), window: handle.window })
+    }
+
+    fn warp(&self, x: i32, y: i32) {
+        // La conexión y la ventana son las de minifb, que vive tanto como el backend
+        unsafe {
+            (self.xlib.XWarpPointer)(self.display, 0, self.window, 0, 0, 0, 0, x, y);
+            (self.xlib.XFlush)(self.display);
+        }
+    }
+}
+
+#[cfg(not(target_os = "linux"))]
+struct PointerWarp;
+
+#[cfg(not(target_os = "linux"))]
+impl PointerWarp {
+    fn new(_window: &Window) -> Option<PointerWarp> {
+        None
+    }
+
+    fn warp(&self, _x: i32, _y: i32) {}
+}
+
+pub struct WindowBackend {
+    window: Window,
+    pointer: Option<PointerWarp>,
+}
+
+impl WindowBackend {
+    pub fn new(title: &str, width: usize, height: usize) -> Self {
+        let window = Window::new(title, width, height, WindowOptions::default()).unwrap();
+        let pointer = PointerWarp::new(&window);
+        WindowBackend { window, pointer }
+    }
+}
+
+impl Backend for WindowBackend {
+    fn is_open(&self) -> bool {
+        self.window.is_open()
+    }
+
+    fn is_key_down(&self, key: Key) -> bool {
+        self.window.is_key_down(key)
+    }
+
+    fn get_mouse_pos(&self) -> Option<(f32, f32)> {
+        // Fuera de la ventana no hay posición, así el giro no salta al volver a entrar
+        self.window.get_mouse_pos(MouseMode::Discard)
+    }
+
+    fn is_mouse_down(&self) -> bool {
+        self.window.get_mouse_down(MouseButton::Left)
+    }
+
+    fn set_cursor_visibility(&mut self, visible: bool) {
+        self.window.set_cursor_visibility(visible);
+    }
+
+    fn warp_mouse(&self, x: f32, y: f32) -> bool {
+        let Some(pointer) = &self.pointer else {
+            return false;
+        };
+        pointer.warp(x as i32, y as i32);
+        true
+    }
+
+    fn update(&mut self, framebuffer: &Framebuffer) {
+        self.window.update_with_buffer(&framebuffer.buffer, framebuffer.width, framebuffer.height).unwrap();
+    }
+}
+
+// Ocupa el lugar de la ventana mientras se cierra la vieja y se abre otra
+pub struct ClosedBackend;
+
+impl Backend for ClosedBackend {
+    fn is_open(&self) -> bool {
+        false
+    }
+
+    fn is_key_down(&self, _key: Key) -> bool {
+        false
+    }
+
+    fn get_mouse_pos(&self) -> Option<(f32, f32)> {
+        None
+    }
+
+    fn is_mouse_down(&self) -> bool {
+        false
+    }
+
+    fn set_cursor_visibility(&mut self, _visible: bool) {}
+
+    fn warp_mouse(&self, _x: f32, _y: f32) -> bool {
+        false
+    }
+
+    fn update(&mut self, _framebuffer: &Framebuffer) {}
+}
+
+// Abre una ventana normal o, si se pidió, dibuja directamente en la terminal.
+pub fn open_backend(title: &str, width: usize, height: usize, terminal: bool) -> Box<dyn Backend> {
+    if terminal {
+        Box::new(TerminalBackend::new(title))
+    } else {
+        Box::new(WindowBackend::new(title, width, height))
+    }
+}
@@ -0,0 +1,271 @@
+use crate::framebuffer::Framebuffer;
+
+// Fuente incorporada de 5x8 píxeles, para el texto de depuración y cuando no hay
+// archivo de fuente. Cada letra ocupa una celda de 5x10: las dos primeras filas
+// quedan para los acentos de las mayúsculas.
+pub const CHAR_WIDTH: usize = 5;
+pub const CHAR_HEIGHT: usize = 10;
+pub const CHAR_ADVANCE: usize = CHAR_WIDTH + 1;
+pub const LINE_HEIGHT: usize = CHAR_HEIGHT + 1;
+
+// ASCII imprimible (0x20 a 0x7E), una columna por byte con el bit 0 arriba
+const ASCII: [[u8; 5]; 95] = [
+    [0x00, 0x00, 0x00, 0x00, 0x00], // espacio
+    [0x00, 0x00, 0x5F, 0x00, 0x00], // !
+    [0x00, 0x07, 0x00, 0x07, 0x00], // "
+    [0x14, 0x7F, 0x14, 0x7F, 0x14], // #
+    [0x24, 0x2A, 0x7F, 0x2A, 0x12], // $
+    [0x23, 0x13, 0x08, 0x64, 0x62], // %
+    [0x36, 0x49, 0x56, 0x20, 0x50], // &
+    [0x00, 0x00, 0x07, 0x00, 0x00], // '
+    [0x00, 0x1C, 0x22, 0x41, 0x00], // (
+    [0x00, 0x41, 0x22, 0x1C, 0x00], // )
+    [0x2A, 0x1C, 0x7F, 0x1C, 0x2A], // *
+    [0x08, 0x08, 0x3E, 0x08, 0x08], // +
+    [0x00, 0x50, 0x30, 0x00, 0x00], // ,
+    [0x08, 0x08, 0x08, 0x08, 0x08], // -
+    [0x00, 0x60, 0x60, 0x00, 0x00], // .
+    [0x20, 0x10, 0x08, 0x04, 0x02], // /
+    [0x3E, 0x51, 0x49, 0x45, 0x3E], // 0
+    [0x00, 0x42, 0x7F, 0x40, 0x00], // 1
+    [0x42, 0x61, 0x51, 0x49, 0x46], // 2
+    [0x21, 0x41, 0x45, 0x4B, 0x31], // 3
+    [0x18, 0x14, 0x12, 0x7F, 0x10], // 4
+    [0x27, 0x45, 0x45, 0x45, 0x39], // 5
+    [0x3C, 0x4A, 0x49, 0x49, 0x30], // 6
+    [0x01, 0x71, 0x09, 0x05, 0x03], // 7
+    [0x36, 0x49, 0x49, 0x49, 0x36], // 8
+    [0x06, 0x49, 0x49, 0x29, 0x1E], // 9
+    [0x00, 0x36, 0x36, 0x00, 0x00], // :
+    [0x00, 0x56, 0x36, 0x00, 0x00], // ;
+    [0x08, 0x14, 0x22, 0x41, 0x00], // <
+    [0x14, 0x14, 0x14, 0x14, 0x14], // =
+    [0x00, 0x41, 0x22, 0x14, 0x08], // >
+    [0x02, 0x01, 0x51, 0x09, 0x06], // ?
+    [0x32, 0x49, 0x79, 0x41, 0x3E], // @
+    [0x7E, 0x11, 0x11, 0x11, 0x7E], // A
+    [0x7F, 0x49, 0x49, 0x49, 0x36], // B
+    [0x3E, 0x41, 0x41, 0x41, 0x22], // C
+    [0x7F, 0x41, 0x41, 0x22, 0x1C], // D
+    [0x7F, 0x49, 0x49, 0x49, 0x41], // E
+    [0x7F, 0x09, 0x09, 0x09, 0x01], // F
+    [0x3E, 0x41, 0x49, 0x49, 0x7A], // G
+    [0x7F, 0x08, 0x08, 0x08, 0x7F], // H
+    [0x00, 0x41, 0x7F, 0x41, 0x00], // I
+    [0x20, 0x40, 0x41, 0x3F, 0x01], // J
+    [0x7F, 0x08, 0x14, 0x22, 0x41], // K
+    [0x7F, 0x40, 0x40, 0x40, 0x40], // L
+    [0x7F, 0x02, 0x0C, 0x02, 0x7F], // M
+    [0x7F, 0x04, 0x08, 0x10, 0x7F], // N
+    [0x3E, 0x41, 0x41, 0x41, 0x3E], // O
+    [0x7F, 0x09, 0x09, 0x09, 0x06], // P
+    [0x3E, 0x41, 0x51, 0x21, 0x5E], // Q
+    [0x7F, 0x09, 0x19, 0x29, 0x46], // R
+    [0x46, 0x49, 0x49, 0x49, 0x31], // S
+    [0x01, 0x01, 0x7F, 0x01, 0x01], // T
+    [0x3F, 0x40, 0x40, 0x40, 0x3F], // U
+    [0x1F, 0x20, 0x40, 0x20, 0x1F], // V
+    [0x3F, 0x40, 0x38, 0x40, 0x3F], // W
+    [0x63, 0x14, 0x08, 0x14, 0x63], // X
+    [0x07, 0x08, 0x70, 0x08, 0x07], // Y
+    [0x61, 0x51, 0x49, 0x45, 0x43], // Z
+    [0x00, 0x7F, 0x41, 0x41, 0x00], // [
+    [0x02, 0x04, 0x08, 0x10, 0x20], // \
+    [0x00, 0x41, 0x41, 0x7F, 0x00], // ]
+    [0x04, 0x02, 0x01, 0x02, 0x04], // ^
+    [0x80, 0x80, 0x80, 0x80, 0x80], // _
+    [0x00, 0x01, 0x02, 0x04, 0x00], // `
+    [0x20, 0x54, 0x54, 0x54, 0x78], // a
+    [0x7F, 0x48, 0x44, 0x44, 0x38], // b
+    [0x38, 0x44, 0x44, 0x44, 0x20], // c
+    [0x38, 0x44, 0x44, 0x48, 0x7F], // d
+    [0x38, 0x54, 0x54, 0x54, 0x18], // e
+    [0x08, 0x7E, 0x09, 0x01, 0x02], // f
+    [0x18, 0xA4, 0xA4, 0xA4, 0x7C], // g
+    [0x7F, 0x08, 0x04, 0x04, 0x78], // h
+    [0x00, 0x44, 0x7D, 0x40, 0x00], // i
+    [0x40, 0x80, 0x84, 0x7D, 0x00], // j
+    [0x7F, 0x10, 0x28, 0x44, 0x00], // k
+    [0x00, 0x41, 0x7F, 0x40, 0x00], // l
+    [0x7C, 0x04, 0x18, 0x04, 0x78], // m
+    [0x7C, 0x08, 0x04, 0x04, 0x78], // n
+    [0x38, 0x44, 0x44, 0x44, 0x38], // o
+    [0xFC, 0x24, 0x24, 0x24, 0x18], // p
+    [0x18, 0x24, 0x24, 0x24, 0xFC], // q
+    [0x7C, 0x08, 0x04, 0x04, 0x08], // r
+    [0x48, 0x54, 0x54, 0x54, 0x20], // s
+    [0x04, 0x3F, 0x44, 0x40, 0x20], // t
+    [0x3C, 0x40, 0x40, 0x20, 0x7C], // u
+    [0x1C, 0x20, 0x40, 0x20, 0x1C], // v
+    [0x3C, 0x40, 0x30, 0x40, 0x3C], // w
+    [0x44, 0x28, 0x10, 0x28, 0x44], // x
+    [0x1C, 0xA0, 0xA0, 0xA0, 0x7C], // y
+    [0x44, 0x64, 0x54, 0x4C, 0x44], // z
+    [0x00, 0x08, 0x36, 0x41, 0x00], // {
+    [0x00, 0x00, 0x7F, 0x00, 0x00], // |
+    [0x00, 0x41, 0x36, 0x08, 0x00], // }
+    [0x08, 0x04, 0x08, 0x10, 0x08], // ~
+];
+
+// Caracteres de Latin-1 que no son letras con acento: signos y letras propias
+const SYMBOLS: [(char, [u8; 5]); 17] = [
+    ('¡', [0x00, 0x00, 0x7D, 0x00, 0x00]),
+    ('¿', [0x30, 0x48, 0x45, 0x40, 0x20]),
+    ('°', [0x00, 0x06, 0x09, 0x09, 0x06]),
+    ('«', [0x08, 0x14, 0x2A, 0x14, 0x22]),
+    ('»', [0x22, 0x14, 0x2A, 0x14, 0x08]),
+    ('·', [0x00, 0x00, 0x08, 0x00, 0x00]),
+    ('×', [0x22, 0x14, 0x08, 0x14, 0x22]),
+    ('÷', [0x08, 0x08, 0x2A, 0x08, 0x08]),
+    ('Æ', [0x7E, 0x11, 0x7F, 0x49, 0x41]),
+    ('Ø', [0x7E, 0x61, 0x59, 0x45, 0x3F]),
+    ('Ð', [0x08, 0x7F, 0x49, 0x41, 0x3E]),
+    ('Þ', [0x7F, 0x22, 0x22, 0x22, 0x1C]),
+    ('ß', [0x7E, 0x01, 0x49, 0x56, 0x20]),
+    ('æ', [0x20, 0x54, 0x78, 0x54, 0x58]),
+    ('ø', [0x78, 0x64, 0x54, 0x4C, 0x3C]),
+    ('ð', [0x20, 0x55, 0x52, 0x55, 0x38]),
+    ('þ', [0xFF, 0x24, 0x24, 0x24, 0x18]),
+];
+
+#[derive(Clone, Copy)]
+enum Mark {
+    Grave,
+    Acute,
+    Circumflex,
+    Tilde,
+    Diaeresis,
+    Ring,
+    Cedilla,
+}
+
+impl Mark {
+    // Dos filas de la marca, una columna por valor con el bit 0 arriba
+    fn columns(self) -> [u16; 5] {
+        match self {
+            Mark::Grave => [0b00, 0b01, 0b10, 0b00, 0b00],
+            Mark::Acute => [0b00, 0b00, 0b10, 0b01, 0b00],
+            Mark::Circumflex => [0b00, 0b10, 0b01, 0b10, 0b00],
+            Mark::Tilde => [0b10, 0b01, 0b10, 0b01, 0b00],
+            Mark::Diaeresis => [0b00, 0b10, 0b00, 0b10, 0b00],
+            Mark::Ring => [0b00, 0b11, 0b01, 0b11, 0b00],
+            Mark::Cedilla => [0b00, 0b10, 0b11, 0b00, 0b00],
+        }
+    }
+}
+
+// Letras acentuadas de Latin-1 como letra base más una marca
+fn decompose(c: char) -> Option<(char, Mark)> {
+    let mark = match c {
+        'À' | 'È' | 'Ì' | 'Ò' | 'Ù' | 'à' | 'è' | 'ì' | 'ò' | 'ù' => Mark::Grave,
+        'Á' | 'É' | 'Í' | 'Ó' | 'Ú' | 'Ý' | 'á' | 'é' | 'í' | 'ó' | 'ú' | 'ý' => Mark::Acute,
+        'Â' | 'Ê' | 'Î' | 'Ô' | 'Û' | 'â' | 'ê' | 'î' | 'ô' | 'û' => Mark::Circumflex,
+        'Ã' | 'Ñ' | 'Õ' | 'ã' | 'ñ' | 'õ' => Mark::Tilde,
+        'Ä' | 'Ë' | 'Ï' | 'Ö' | 'Ü' | 'ä' | 'ë' | 'ï' | 'ö' | 'ü' | 'ÿ' => Mark::Diaeresis,
+        'Å' | 'å' => Mark::Ring,
+        'Ç' | 'ç' => Mark::Cedilla,
+        _ => return None,
+    };
+    Some((strip(c), mark))
+}
+
+// La letra sin su marca
+fn strip(c: char) -> char {
+    match c {
+        'À'..='Å' => 'A',
+        'Ç' => 'C',
+        'È'..='Ë' => 'E',
+        'Ì'..='Ï' => 'I',
+        'Ñ' => 'N',
+        'Ò'..='Ö' => 'O',
+        'Ù'..='Ü' => 'U',
+        'Ý' => 'Y',
+        'à'..='å' => 'a',
+        'ç' => 'c',
+        'è'..='ë' => 'e',
+        'ì'..='ï' => 'i',
+        'ñ' => 'n',
+        'ò'..='ö' => 'o',
+        'ù'..='ü' => 'u',
+        'ý' | 'ÿ' => 'y',
+        other => other,
+    }
+}
+
+// Columnas de la celda de 5x10 (bit 0 arriba); None si la fuente no tiene el carácter
+pub fn glyph(c: char) -> Option<[u16; 5]> {
+    let ascii = |c: char| (' '..='~').contains(&c).then(|| ASCII[c as usize - ' ' as usize]);
+    let shifted = |bytes: [u8; 5]| bytes.map(|column| (column as u16) << 2);
+
+    if let Some(bytes) = ascii(c).or_else(|| SYMBOLS.iter().find(|(symbol, _)| *symbol == c).map(|(_, bytes)| *bytes)) {
+        return Some(shifted(bytes));
+    }
+
+    let (base, mark) = decompose(c)?;
+    let mut columns = shifted(ascii(base)?);
+    let marks = mark.columns();
+    for (column, mark_bits) in columns.iter_mut().zip(marks) {
+        *column = match mark {
+            // La cedilla va debajo de la letra
+            Mark::Cedilla => *column | mark_bits << 8,
+            // Las minúsculas pierden su punto (la i) y llevan la marca justo encima del cuerpo
+            _ if base.is_ascii_lowercase() => (*column & !0b1100) | (mark_bits << 1),
+            _ => *column | mark_bits,
+        };
+    }
+    Some(columns)
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct BitmapStyle {
+    pub color: u32,
+    pub scale: usize, // Cada píxel de la fuente ocupa scale x scale píxeles
+    pub shadow: Option<u32>, // Color de una sombra desplazada un píxel abajo a la derecha
+    pub outline: Option<u32>, // Color de un borde de un píxel alrededor de cada letra
+}
+
+impl Default for BitmapStyle {
+    fn default() -> Self {
+        BitmapStyle { color: 0xFFFFFF, scale: 1, shadow: None, outline: None }
+    }
+}
+
+// Ancho y alto que ocupa el texto, con un salto de línea por cada '\n'
+pub fn bitmap_text_size(text: &str, scale: usize) -> (usize, usize) {
+    let columns = text.split('\n').map(|line| line.chars().count()).max().unwrap_or(0);
+    let lines = text.split('\n').count();
+    (columns * CHAR_ADVANCE * scale, lines * LINE_HEIGHT * scale)
+}
+
+// Dibuja el texto con `x`, `y` en la esquina superior izquierda de la primera celda
+pub fn draw_bitmap_text(framebuffer: &mut Framebuffer, text: &str, x: i32, y: i32, style: &BitmapStyle) {
+    let scale = style.scale.max(1) as i32;
+
+    if let Some(outline) = style.outline {
+        for (dx, dy) in [(-1, -1), (0, -1), (1, -1), (-1, 0), (1, 0), (-1, 1), (0, 1), (1, 1)] {
+            draw_layer(framebuffer, text, x + dx * scale, y + dy * scale, scale, outline);
+        }
+    }
+    if let Some(shadow) = style.shadow {
+        draw_layer(framebuffer, text, x + scale, y + scale, scale, shadow);
+    }
+    draw_layer(framebuffer, text, x, y, scale, style.color);
+}
+
+fn draw_layer(framebuffer: &mut Framebuffer, text: &str, x: i32, y: i32, scale: i32, color: u32) {
+    for (row, line) in text.split('\n').enumerate() {
+        let top = y + (row * LINE_HEIGHT) as i32 * scale;
+        for (index, c) in line.chars().enumerate() {
+            let left = x + (index * CHAR_ADVANCE) as i32 * scale;
+            // Los caracteres que la fuente no tiene se ven como un rectángulo vacío
+            let columns = glyph(c).unwrap_or([0x3FC, 0x204, 0x204, 0x204, 0x3FC]);
+            for (col, bits) in columns.iter().enumerate() {
+                for bit in 0..CHAR_HEIGHT {
+                    if bits & (1 << bit) != 0 {
+                        framebuffer.fill_rect(left + col as i32 * scale, top + bit as i32 * scale, scale as usize, scale as usize, color);
+                    }
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,106 @@
+use std::f32::consts::PI;
+
+use crate::input::Action;
+use crate::maze::Maze;
+use crate::player::{apply_input, InputFrame, Player, KEY_ROTATION_SPEED, MOVE_SPEED, TICK};
+use crate::solver::{shortest_path, Cell};
+
+// Pasos máximos por celda del camino antes de dar al bot por atascado
+const TICKS_PER_CELL: usize = 60;
+
+// Conductor automático: sigue el camino más corto con las mismas acciones que
+// un jugador, así pasa por apply_input y sus reglas de colisión igual que él.
+pub struct Bot {
+    path: Vec<Cell>,
+    next: usize,
+}
+
+impl Bot {
+    // None si desde la posición del jugador no se llega a la meta
+    pub fn new(maze: &Maze, player: &Player) -> Option<Bot> {
+        let cell = ((player.pos.x as usize) / maze.block_size, (player.pos.y as usize) / maze.block_size);
+        let path = shortest_path(maze, cell)?;
+        Some(Bot { path, next: 1 })
+    }
+
+    pub fn is_done(&self) -> bool {
+        self.next >= self.path.len()
+    }
+
+    // Entrada del próximo paso: girar hacia el centro de la próxima celda y avanzar
+    pub fn steer(&mut self, maze: &Maze, player: &Player) -> InputFrame {
+        let mut input = InputFrame { dt: TICK, ..InputFrame::default() };
+
+        while let Some(&(col, row)) = self.path.get(self.next) {
+            if (maze.cell_center(col, row) - player.pos).magnitude() >= MOVE_SPEED * TICK {
+                break;
+            }
+            self.next += 1;
+        }
+        let Some(&(col, row)) = self.path.get(self.next) else {
+            return input;
+        };
+
+        let offset = maze.cell_center(col, row) - player.pos;
+        let turn = (offset.y.atan2(offset.x) - player.a + PI).rem_euclid(2.0 * PI) - PI;
+
+        let turn_step = KEY_ROTATION_SPEED * TICK;
+        if turn > turn_step / 2.0 {
+            input.press(Action::TurnRight);
+        } else if turn < -turn_step / 2.0 {
+            input.press(Action::TurnLeft);
+        }
+
+        // Solo avanza cuando ya mira más o menos hacia la celda
+        if turn.abs() < 0.3 {
+            input.press(Action::MoveForward);
+        }
+        input
+    }
+}
+
+// Partida jugada por el bot, para el modo demostración del menú y las pruebas
+pub struct Demo {
+    pub maze: Maze,
+    pub player: Player,
+    bot: Bot,
+    ticks: usize,
+    accumulator: f32,
+}
+
+impl Demo {
+    pub fn new(maze: Maze) -> Option<Demo> {
+        let player = Player::at_spawn(&maze);
+        let bot = Bot::new(&maze, &player)?;
+        Some(Demo { maze, player, bot, ticks: 0, accumulator: 0.0 })
+    }
+
+    pub fn is_won(&self) -> bool {
+        let col = (self.player.pos.x as usize) / self.maze.block_size;
+        let row = (self.player.pos.y as usize) / self.maze.block_size;
+        self.maze.is_near_goal(col, row)
+    }
+
+    // Avanza un paso de la simulación; devuelve false cuando la partida terminó (ganada o atascada)
+    pub fn step(&mut self) -> bool {
+        if self.is_won() || self.bot.is_done() || self.ticks > self.bot.path.len() * TICKS_PER_CELL {
+            return false;
+        }
+        let input = self.bot.steer(&self.maze, &self.player);
+        apply_input(&mut self.player, &self.maze, &input);
+        self.ticks += 1;
+        true
+    }
+
+    // Avanza los pasos que correspondan a `dt` segundos reales
+    pub fn update(&mut self, dt: f32) -> bool {
+        self.accumulator += dt;
+        while self.accumulator >= TICK {
+            self.accumulator -= TICK;
+            if !self.step() {
+                return false;
+            }
+        }
+        true
+    }
+}
@@ -0,0 +1,125 @@
+use crate::generator::Algorithm;
+use crate::settings::parse_resolution;
+
+pub const USAGE: &str = "\
+Uso: Raycasting [opciones] [nivel]
+       Raycasting validate [archivos...]
+
+El nivel puede ser un archivo de laberinto, \"algoritmo:semilla\" o \"daily:AAAA-MM-DD\".
+Sin nivel el juego empieza en el menú principal.
+
+Opciones:
+  --level <nivel>          Empieza directamente en ese nivel
+  --seed <semilla>         Empieza en un laberinto generado con esa semilla
+  --algorithm <nombre>     Algoritmo para --seed: backtracker, prim, kruskal, eller o wilson
+  --backend <tipo>         window (por defecto), terminal o headless
+  --terminal               Igual que --backend terminal
+  --headless               Igual que --backend headless: repite una grabación sin pantalla
+  --resolution <AxB>       Tamaño de la ventana, por ejemplo 1600x900
+  --no-audio               Sin música ni efectos
+  --record <archivo>       Guarda la entrada de la partida
+  --replay <archivo>       Vuelve a jugar una partida grabada
+  -h, --help               Muestra esta ayuda
+
+Subcomandos:
+  validate [archivos...]   Revisa que los laberintos se puedan completar (sin archivos, todos los de mazes/)";
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum BackendKind {
+    Window,
+    Terminal,
+    Headless, // Sin pantalla ni entrada: solo sirve para repetir grabaciones
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct PlayOptions {
+    pub level: Option<String>, // Mismo formato que Level::path
+    pub backend: BackendKind,
+    pub resolution: Option<(usize, usize)>,
+    pub no_audio: bool,
+    pub record: Option<String>,
+    pub replay: Option<String>,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub enum Command {
+    Play(PlayOptions),
+    Validate(Vec<String>),
+    Help,
+}
+
+// Interpreta los argumentos (sin el nombre del programa); el error explica qué está mal
+pub fn parse(args: &[String]) -> Result<Command, String> {
+    if args.first().map(String::as_str) == Some("validate") {
+        return Ok(Command::Validate(args[1..].to_vec()));
+    }
+
+    let mut options = PlayOptions { level: None, backend: BackendKind::Window, resolution: None, no_audio: false, record: None, replay: None };
+    let mut seed: Option<u64> = None;
+    let mut algorithm: Option<Algorithm> = None;
+
+    let mut args = args.iter();
+    while let Some(arg) = args.next() {
+        // Valor que sigue a una opción, como el archivo en "--record partida.rec"
+        let mut value = || args.next().ok_or_else(|| format!("falta el valor de {}", arg));
+
+        match arg.as_str() {
+            "-h" | "--help" => return Ok(Command::Help),
+            "--level" => set_level(&mut options, value()?)?,
+            "--seed" => {
+                let text = value()?;
+                seed = Some(text.parse().map_err(|_| format!("semilla inválida: {:?}", text))?);
+            }
+            "--algorithm" => {
+                let name = value()?;
+                algorithm = Some(Algorithm::from_name(name).ok_or_else(|| format!("algoritmo desconocido: {:?}", name))?);
+            }
+            "--backend" => {
+                options.backend = match value()?.as_str() {
+                    "window" => BackendKind::Window,
+                    "terminal" => BackendKind::Terminal,
+                    "headless" => BackendKind::Headless,
+                    other => return Err(format!("backend desconocido: {:?}", other)),
+                }
+            }
+            "--terminal" => options.backend = BackendKind::Terminal,
+            "--headless" => options.backend = BackendKind::Headless,
+            "--resolution" => {
+                let text = value()?;
+                options.resolution = Some(parse_resolution(text).ok_or_else(|| format!("resolución inválida: {:?}", text))?);
+            }
+            "--no-audio" => options.no_audio = true,
+            "--record" => options.record = Some(value()?.clone()),
+            "--replay" => options.replay = Some(value()?.clone()),
+            flag if flag.starts_with('-') => return Err(format!("opción desconocida: {}", flag)),
+            level => set_level(&mut options, level)?,
+        }
+    }
+
+    if let Some(seed) = seed {
+        // Sin algoritmo se elige con la semilla, como en los laberintos aleatorios del menú
+        let algorithm = algorithm.unwrap_or(Algorithm::ALL[(seed % Algorithm::ALL.len() as u64) as usize]);
+        set_level(&mut options, &format!("{}:{}", algorithm.name(), seed))?;
+    } else if algorithm.is_some() {
+        return Err("--algorithm necesita --seed".to_string());
+    }
+
+    if options.replay.is_some() && options.level.is_some() {
+        return Err("una grabación ya indica su nivel; no se puede elegir otro".to_string());
+    }
+    if options.replay.is_some() && options.record.is_some() {
+        return Err("no se puede grabar mientras se repite una grabación".to_string());
+    }
+    if options.backend == BackendKind::Headless && options.replay.is_none() {
+        return Err("--headless necesita --replay".to_string());
+    }
+
+    Ok(Command::Play(options))
+}
+
+fn set_level(options: &mut PlayOptions, level: &str) -> Result<(), String> {
+    if options.level.replace(level.to_string()).is_some() {
+        return Err("solo se puede elegir un nivel".to_string());
+    }
+    Ok(())
+}
@@ -0,0 +1,118 @@
+use std::fmt;
+use std::fs::{self, OpenOptions};
+use std::io::{self, Write};
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use crate::generator::{Algorithm, Rng};
+use crate::input::config_dir;
+
+// Tiempos del reto diario en la carpeta de configuración, una línea por partida terminada
+pub const DAILY_RECORDS_FILE: &str = "daily_records.txt";
+
+// El reto diario es más grande que el laberinto aleatorio normal
+pub const DAILY_WIDTH: usize = 16;
+pub const DAILY_HEIGHT: usize = 12;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Date {
+    pub year: i32,
+    pub month: u32,
+    pub day: u32,
+}
+
+impl Date {
+    // Se usa la fecha UTC para que todo el equipo tenga el mismo laberinto el mismo día
+    pub fn today() -> Date {
+        let seconds = SystemTime::now().duration_since(UNIX_EPOCH).map_or(0, |elapsed| elapsed.as_secs());
+        Date::from_days((seconds / 86_400) as i64)
+    }
+
+    // Días desde 1970-01-01 a fecha del calendario gregoriano
+    pub fn from_days(days: i64) -> Date {
+        let z = days + 719_468;
+        let era = if z >= 0 { z } else { z - 146_096 } / 146_097;
+        let doe = z - era * 146_097;
+        let yoe = (doe - doe / 1_460 + doe / 36_524 - doe / 146_096) / 365;
+        let doy = doe - (365 * yoe + yoe / 4 - yoe / 100);
+        let mp = (5 * doy + 2) / 153;
+        let day = (doy - (153 * mp + 2) / 5 + 1) as u32;
+        let month = if mp < 10 { mp + 3 } else { mp - 9 } as u32;
+        let year = (yoe + era * 400 + i64::from(month <= 2)) as i32;
+        Date { year, month, day }
+    }
+
+    // "AAAA-MM-DD"; solo fechas que existen en el calendario
+    pub fn parse(text: &str) -> Option<Date> {
+        let mut parts = text.splitn(3, '-');
+        let year = parts.next()?.parse().ok()?;
+        let month = parts.next()?.parse().ok()?;
+        let day = parts.next()?.parse().ok()?;
+        ((1..=12).contains(&month) && (1..=days_in_month(year, month)).contains(&day)).then_some(Date { year, month, day })
+    }
+
+    pub fn seed(&self) -> u64 {
+        let number = self.year as u64 * 10_000 + self.month as u64 * 100 + self.day as u64;
+        Rng::new(number).next_u64()
+    }
+
+    pub fn algorithm(&self) -> Algorithm {
+        Algorithm::ALL[(self.seed() % Algorithm::ALL.len() as u64) as usize]
+    }
+}
+
+fn is_leap_year(year: i32) -> bool {
+    year % 4 == 0 && (year % 100 != 0 || year % 400 == 0)
+}
+
+fn days_in_month(year: i32, month: u32) -> u32 {
+    match month {
+        2 if is_leap_year(year) => 29,
+        2 => 28,
+        4 | 6 | 9 | 11 => 30,
+        _ => 31,
+    }
+}
+
+impl fmt::Display for Date {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{:04}-{:02}-{:02}", self.year, self.month, self.day)
+    }
+}
+
+pub struct DailyRecord {
+    pub date: Date,
+    pub seconds: f32,
+    pub player: String,
+}
+
+fn player_name() -> String {
+    std::env::var("USER").or_else(|_| std::env::var("USERNAME")).unwrap_or_else(|_| "jugador".to_string())
+}
+
+pub fn record_time(date: Date, seconds: f32) -> io::Result<()> {
+    let dir = config_dir().ok_or_else(|| io::Error::new(io::ErrorKind::NotFound, "no hay carpeta de configuración"))?;
+    fs::create_dir_all(&dir)?;
+    let mut file = OpenOptions::new().create(true).append(true).open(dir.join(DAILY_RECORDS_FILE))?;
+    writeln!(file, "{}\t{:.2}\t{}", date, seconds, player_name())
+}
+
+// Tiempos de un día, del más rápido al más lento; las líneas dañadas se ignoran
+pub fn times_for(date: Date) -> Vec<DailyRecord> {
+    let path = config_dir().map(|dir| dir.join(DAILY_RECORDS_FILE));
+    let text = path.and_then(|path| fs::read_to_string(path).ok()).unwrap_or_default();
+
+    let mut records: Vec<DailyRecord> = text
+        .lines()
+        .filter_map(|line| {
+            let mut fields = line.split('\t');
+            let date = Date::parse(fields.next()?)?;
+            let seconds = fields.next()?.parse().ok()?;
+            let player = fields.next().unwrap_or_default().to_string();
+            Some(DailyRecord { date, seconds, player })
+        })
+        .filter(|record| record.date == date)
+        .collect();
+
+    records.sort_by(|a, b| a.seconds.total_cmp(&b.seconds));
+    records
+}
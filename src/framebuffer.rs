@@ -1,3 +1,12 @@
+use image::RgbaImage;
+
+// Cómo se toma el color de una imagen escalada
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Filter {
+    Nearest,  // El píxel más cercano: bordes duros, como pixel art
+    Bilinear, // Mezcla de los cuatro píxeles vecinos; al reducir, promedio de todos los que cubre
+}
+
 pub struct Framebuffer {
     pub width: usize,
     pub height: usize,
@@ -43,4 +52,221 @@ impl Framebuffer {
     pub fn set_current_color(&mut self, color: u32) {
         self.current_color = color;
     }
+
+    // Oscurece toda la imagen; `factor` 0 la deja negra y 1 igual
+    pub fn dim(&mut self, factor: f32) {
+        for pixel in self.buffer.iter_mut() {
+            let channel = |shift: u32| ((((*pixel >> shift) & 0xFF) as f32 * factor) as u32) << shift;
+            *pixel = channel(16) | channel(8) | channel(0);
+        }
+    }
+
+    fn index(&self, x: i32, y: i32) -> Option<usize> {
+        let inside = x >= 0 && y >= 0 && (x as usize) < self.width && (y as usize) < self.height;
+        inside.then(|| y as usize * self.width + x as usize)
+    }
+
+    // Las primitivas reciben su color y recortan lo que queda fuera de la imagen
+    pub fn set_pixel(&mut self, x: i32, y: i32, color: u32) {
+        if let Some(index) = self.index(x, y) {
+            self.buffer[index] = color;
+        }
+    }
+
+    // Mezcla `color` sobre el píxel con opacidad `alpha`, de 0 (nada) a 1 (lo reemplaza)
+    pub fn blend_pixel(&mut self, x: i32, y: i32, color: u32, alpha: f32) {
+        let Some(index) = self.index(x, y) else {
+            return;
+        };
+        if alpha <= 0.0 {
+            return;
+        }
+        let alpha = alpha.min(1.0);
+        let background = self.buffer[index];
+        let mix = |shift: u32| {
+            let front = ((color >> shift) & 0xFF) as f32;
+            let back = ((background >> shift) & 0xFF) as f32;
+            ((back + (front - back) * alpha).round() as u32) << shift
+        };
+        self.buffer[index] = mix(16) | mix(8) | mix(0);
+    }
+
+    pub fn fill_rect(&mut self, x: i32, y: i32, width: usize, height: usize, color: u32) {
+        let (x0, y0) = (x.max(0) as i64, y.max(0) as i64);
+        let x1 = (x as i64 + width as i64).min(self.width as i64);
+        let y1 = (y as i64 + height as i64).min(self.height as i64);
+        if x0 >= x1 || y0 >= y1 {
+            return;
+        }
+        for row in y0 as usize..y1 as usize {
+            let start = row * self.width;
+            self.buffer[start + x0 as usize..start + x1 as usize].fill(color);
+        }
+    }
+
+    // Borde de un pixel de grosor
+    pub fn rect(&mut self, x: i32, y: i32, width: usize, height: usize, color: u32) {
+        if width == 0 || height == 0 {
+            return;
+        }
+        let (right, bottom) = (x + width as i32 - 1, y + height as i32 - 1);
+        self.fill_rect(x, y, width, 1, color);
+        self.fill_rect(x, bottom, width, 1, color);
+        self.fill_rect(x, y, 1, height, color);
+        self.fill_rect(right, y, 1, height, color);
+    }
+
+    // Bresenham, con los dos extremos incluidos
+    pub fn line(&mut self, x0: i32, y0: i32, x1: i32, y1: i32, color: u32) {
+        let (dx, dy) = ((x1 - x0).abs(), -(y1 - y0).abs());
+        let (step_x, step_y) = (if x0 < x1 { 1 } else { -1 }, if y0 < y1 { 1 } else { -1 });
+        let (mut x, mut y, mut error) = (x0, y0, dx + dy);
+        loop {
+            self.set_pixel(x, y, color);
+            if x == x1 && y == y1 {
+                break;
+            }
+            let double = 2 * error;
+            if double >= dy {
+                error += dy;
+                x += step_x;
+            }
+            if double <= dx {
+                error += dx;
+                y += step_y;
+            }
+        }
+    }
+
+    pub fn fill_circle(&mut self, cx: i32, cy: i32, radius: i32, color: u32) {
+        for dy in -radius..=radius {
+            let dx = ((radius * radius - dy * dy) as f32).sqrt() as i32;
+            self.fill_rect(cx - dx, cy + dy, (2 * dx + 1) as usize, 1, color);
+        }
+    }
+
+    // Circunferencia por el algoritmo del punto medio
+    pub fn circle(&mut self, cx: i32, cy: i32, radius: i32, color: u32) {
+        if radius < 0 {
+            return;
+        }
+        let (mut x, mut y, mut error) = (radius, 0, 1 - radius);
+        while x >= y {
+            for (px, py) in [(x, y), (y, x), (-y, x), (-x, y), (-x, -y), (-y, -x), (y, -x), (x, -y)] {
+                self.set_pixel(cx + px, cy + py, color);
+            }
+            y += 1;
+            if error < 0 {
+                error += 2 * y + 1;
+            } else {
+                x -= 1;
+                error += 2 * (y - x) + 1;
+            }
+        }
+    }
+
+    // Copia otro framebuffer con su esquina superior izquierda en (x, y)
+    pub fn copy_from(&mut self, source: &Framebuffer, x: i32, y: i32) {
+        let (sx0, sy0) = ((-x).max(0) as usize, (-y).max(0) as usize);
+        let sx1 = source.width.min((self.width as i64 - x as i64).max(0) as usize);
+        let sy1 = source.height.min((self.height as i64 - y as i64).max(0) as usize);
+        if sx0 >= sx1 {
+            return;
+        }
+        for sy in sy0..sy1 {
+            let from = sy * source.width;
+            let to = (y as i64 + sy as i64) as usize * self.width + (x as i64 + sx0 as i64) as usize;
+            self.buffer[to..to + sx1 - sx0].copy_from_slice(&source.buffer[from + sx0..from + sx1]);
+        }
+    }
+
+    // Dibuja la imagen estirada a width x height con la esquina en (x, y), mezclando
+    // según su transparencia. Cada píxel de destino busca su color en la imagen, así
+    // que sirve para cualquier escala.
+    pub fn blit(&mut self, image: &RgbaImage, x: i32, y: i32, width: usize, height: usize, filter: Filter) {
+        if image.width() == 0 || image.height() == 0 || width == 0 || height == 0 {
+            return;
+        }
+        let scale_x = image.width() as f32 / width as f32;
+        let scale_y = image.height() as f32 / height as f32;
+        let shrinking = scale_x > 1.0 || scale_y > 1.0;
+
+        // Solo se recorre la parte del destino que cae dentro del framebuffer
+        let columns = (-x).max(0) as i64..(width as i64).min(self.width as i64 - x as i64);
+        let rows = (-y).max(0) as i64..(height as i64).min(self.height as i64 - y as i64);
+        for dy in rows {
+            for dx in columns.clone() {
+                // Centro del píxel de destino, en coordenadas de la imagen
+                let sx = (dx as f32 + 0.5) * scale_x;
+                let sy = (dy as f32 + 0.5) * scale_y;
+                let (color, alpha) = match filter {
+                    Filter::Nearest => sample_nearest(image, sx, sy),
+                    // Al reducir, cuatro vecinos saltean píxeles y la imagen parpadea al cambiar de tamaño
+                    Filter::Bilinear if shrinking => {
+                        let (half_x, half_y) = (scale_x.max(1.0) / 2.0, scale_y.max(1.0) / 2.0);
+                        sample_area(image, sx - half_x, sy - half_y, sx + half_x, sy + half_y)
+                    }
+                    Filter::Bilinear => sample_bilinear(image, sx - 0.5, sy - 0.5),
+                };
+                self.blend_pixel(x + dx as i32, y + dy as i32, color, alpha);
+            }
+        }
+    }
+}
+
+// Color (0xRRGGBB) y opacidad (0 a 1) del píxel que contiene el punto
+fn sample_nearest(image: &RgbaImage, x: f32, y: f32) -> (u32, f32) {
+    let px = (x as u32).min(image.width() - 1);
+    let py = (y as u32).min(image.height() - 1);
+    let [r, g, b, a] = image.get_pixel(px, py).0;
+    (((r as u32) << 16) | ((g as u32) << 8) | b as u32, a as f32 / 255.0)
+}
+
+// Promedio de los cuatro píxeles alrededor del punto, pesado por distancia
+fn sample_bilinear(image: &RgbaImage, x: f32, y: f32) -> (u32, f32) {
+    let (max_x, max_y) = (image.width() as i64 - 1, image.height() as i64 - 1);
+    let (left, top) = (x.floor(), y.floor());
+    let (fx, fy) = (x - left, y - top);
+
+    average([(0, 0, (1.0 - fx) * (1.0 - fy)), (1, 0, fx * (1.0 - fy)), (0, 1, (1.0 - fx) * fy), (1, 1, fx * fy)].map(|(ox, oy, weight)| {
+        let px = (left as i64 + ox).clamp(0, max_x) as u32;
+        let py = (top as i64 + oy).clamp(0, max_y) as u32;
+        (image.get_pixel(px, py).0, weight)
+    }))
+}
+
+// Promedio de los píxeles dentro del rectángulo de (x0, y0) a (x1, y1), cada uno
+// pesado por la parte que queda adentro
+fn sample_area(image: &RgbaImage, x0: f32, y0: f32, x1: f32, y1: f32) -> (u32, f32) {
+    let (x0, y0) = (x0.max(0.0), y0.max(0.0));
+    let (x1, y1) = (x1.min(image.width() as f32), y1.min(image.height() as f32));
+    let columns = x0 as u32..(x1.ceil() as u32).min(image.width());
+    let rows = y0 as u32..(y1.ceil() as u32).min(image.height());
+
+    average(rows.flat_map(|py| columns.clone().map(move |px| (px, py))).map(|(px, py)| {
+        let covered_x = x1.min(px as f32 + 1.0) - x0.max(px as f32);
+        let covered_y = y1.min(py as f32 + 1.0) - y0.max(py as f32);
+        (image.get_pixel(px, py).0, covered_x.max(0.0) * covered_y.max(0.0))
+    }))
+}
+
+// Mezcla píxeles RGBA con esos pesos. El color se pesa también por la opacidad para
+// que los píxeles transparentes no oscurezcan los bordes.
+fn average(samples: impl IntoIterator<Item = ([u8; 4], f32)>) -> (u32, f32) {
+    let mut sum = [0.0f32; 3];
+    let (mut alpha, mut total) = (0.0, 0.0);
+    for (pixel, weight) in samples {
+        let opaque = weight * pixel[3] as f32 / 255.0;
+        for channel in 0..3 {
+            sum[channel] += pixel[channel] as f32 * opaque;
+        }
+        alpha += opaque;
+        total += weight;
+    }
+
+    if alpha <= 0.0 {
+        return (0, 0.0);
+    }
+    let channel = |index: usize| ((sum[index] / alpha).round() as u32).min(255);
+    ((channel(0) << 16) | (channel(1) << 8) | channel(2), alpha / total)
 }
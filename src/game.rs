@@ -0,0 +1,587 @@
+use std::time::{Duration, Instant};
+
+use image::RgbaImage;
+use minifb::Key;
+
+use crate::backend::{open_backend, Backend, ClosedBackend};
+use crate::bitmap_font::{bitmap_text_size, draw_bitmap_text, BitmapStyle};
+use crate::bot::Demo;
+use crate::daily::{record_time, times_for, DailyRecord, Date};
+use crate::framebuffer::Framebuffer;
+use crate::input::{Action, Bindings, KeyEdges, MouseLook};
+use crate::levels::{discover_levels, Level, LEVELS_DIR};
+use crate::maze::MazeError;
+use crate::menu::{ControlsMenu, LevelMenu, OptionsMenu, SettingsEvent, SettingsMenu};
+use crate::player::{InputFrame, TICK};
+use crate::render::{draw_image, render3d};
+use crate::replay::Recording;
+use crate::session::{Outcome, Session};
+use crate::settings::Settings;
+use crate::text::{draw_string, Align, TextStyle};
+use crate::sounds::{pause_music, play_background_music, play_victory_sound, resume_music, set_volumes, stop_music};
+
+const MENU_MUSIC: &str = "src/assets/music/Menu_Music.mp3";
+const MENU_IMAGE: &str = "src/assets/images/menu_image.png";
+const VICTORY_IMAGE: &str = "src/assets/images/victory_image.png";
+
+// Un cuadro muy lento (por ejemplo al mover la ventana) no debe adelantar la simulación de golpe
+const MAX_FRAME_TIME: f32 = 0.25;
+
+// Sin tocar el menú durante este tiempo, el bot juega un laberinto aleatorio de demostración
+const ATTRACT_DELAY: Duration = Duration::from_secs(20);
+
+const OPTIONS_X: usize = 60;
+const OPTIONS_Y: usize = 320;
+
+const MAIN_OPTIONS: [&str; 3] = ["Jugar", "Configuración", "Salir"];
+const PAUSE_OPTIONS: [&str; 4] = ["Continuar", "Reiniciar", "Configuración", "Salir al menú"];
+const PAUSE_DIM: f32 = 0.4; // Brillo del juego detrás del menú de pausa
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum State {
+    Menu,
+    LevelSelect,
+    Playing,
+    Paused,
+    Victory,
+    Defeat,
+    Settings,
+}
+
+// Cómo arranca el juego según la línea de comandos
+pub struct GameOptions {
+    pub settings: Settings,
+    pub use_terminal: bool,
+    pub level: Option<String>, // Nivel con el que empezar en lugar del menú
+    pub record_path: Option<String>,
+    pub replay: Option<Recording>,
+}
+
+// Todo el juego en una sola ventana: cada cuadro lo atiende el estado actual,
+// que puede pedir pasar a otro.
+pub struct Game {
+    window: Box<dyn Backend>,
+    framebuffer: Framebuffer,
+    state: State,
+    running: bool,
+    use_terminal: bool,
+    record_path: Option<String>,
+
+    settings: Settings,
+    bindings: Bindings,
+    mouse: MouseLook,
+    keys: KeyEdges,
+    previous_input: InputFrame,
+
+    music: Option<String>, // Música que está sonando
+    menu_image: Option<RgbaImage>,
+    victory_image: Option<RgbaImage>,
+    last_frame_time: Instant,
+
+    main_menu: OptionsMenu,
+    level_menu: LevelMenu,
+    demo: Option<Demo>,
+    session: Option<Session>,
+    pause_menu: OptionsMenu,
+    paused_frame: Vec<u32>, // Último cuadro del juego, oscurecido, detrás del menú de pausa
+    settings_menu: SettingsMenu,
+    controls: Option<ControlsMenu>, // Pantalla de controles, abierta desde la configuración
+    settings_return: State, // Estado al que se vuelve al cerrar la configuración
+    results: Vec<DailyRecord>,
+}
+
+impl Game {
+    pub fn new(options: GameOptions) -> Game {
+        let settings = options.settings;
+        let window = open_backend("BRAT MAZE", settings.width, settings.height, options.use_terminal);
+        let framebuffer = Framebuffer::new(settings.width, settings.height);
+
+        // Todos los niveles de la carpeta mazes/ (o de su manifiesto), uno aleatorio y el reto diario
+        let mut levels = discover_levels(LEVELS_DIR);
+        levels.push(Level::random());
+        levels.push(Level::daily(Date::today()));
+
+        let open_image = |path: &str| match image::open(path) {
+            Ok(image) => Some(image.to_rgba8()),
+            Err(err) => {
+                eprintln!("No se pudo abrir la imagen {}: {}", path, err);
+                None
+            }
+        };
+
+        let mut game = Game {
+            window,
+            mouse: MouseLook::new(framebuffer.width),
+            framebuffer,
+            state: State::Menu,
+            running: true,
+            use_terminal: options.use_terminal,
+            record_path: options.record_path,
+            settings_menu: SettingsMenu::new(settings.clone()),
+            settings,
+            controls: None,
+            bindings: Bindings::load(),
+            keys: KeyEdges::default(),
+            previous_input: InputFrame::default(),
+            music: None,
+            menu_image: open_image(MENU_IMAGE),
+            victory_image: open_image(VICTORY_IMAGE),
+            last_frame_time: Instant::now(),
+            main_menu: OptionsMenu::new(&MAIN_OPTIONS),
+            level_menu: LevelMenu::new(levels),
+            demo: None,
+            session: None,
+            pause_menu: OptionsMenu::new(&PAUSE_OPTIONS),
+            paused_frame: Vec::new(),
+            settings_return: State::Menu,
+            results: Vec::new(),
+        };
+        game.apply_settings();
+
+        // Una repetición empieza directamente en el nivel grabado, igual que un nivel pedido al lanzar el juego
+        let started = match (options.replay, options.level) {
+            (Some(recording), _) => game.start(Level::from_path(&recording.level), Some(recording)),
+            (None, Some(path)) => game.start(Level::from_path(&path), None),
+            (None, None) => false,
+        };
+        if !started {
+            game.go(State::Menu);
+        }
+        game
+    }
+
+    pub fn run(&mut self) {
+        let mut last_tick = Instant::now();
+
+        while self.running && self.window.is_open() {
+            // Tiempo real desde el cuadro anterior, acotado para no quedar atrapados si un cuadro tarda mucho
+            let frame_time = last_tick.elapsed().as_secs_f32().min(MAX_FRAME_TIME);
+            last_tick = Instant::now();
+
+            let next = match self.state {
+                State::Menu => self.update_menu(),
+                State::LevelSelect => self.update_level_select(frame_time),
+                State::Playing => self.update_playing(frame_time),
+                State::Paused => self.update_paused(),
+                State::Victory => self.update_victory(),
+                State::Defeat => self.update_defeat(),
+                State::Settings => self.update_settings(),
+            };
+
+            self.window.update(&self.framebuffer);
+            if let Some(state) = next {
+                self.go(state);
+            }
+        }
+    }
+
+    // Cambia de estado; aquí se decide la música y qué pasa con la partida en curso
+    fn go(&mut self, state: State) {
+        let from = self.state;
+        self.state = state;
+        self.keys.block();
+        self.window.set_cursor_visibility(state != State::Playing);
+
+        // La música sigue pausada mientras la configuración se abre desde la pausa
+        if from == State::Paused && state != State::Settings {
+            resume_music();
+        }
+
+        match state {
+            State::Menu | State::LevelSelect => {
+                if matches!(from, State::Playing | State::Paused) {
+                    self.save_recording();
+                }
+                self.set_music(Some(MENU_MUSIC));
+                self.main_menu = OptionsMenu::new(&MAIN_OPTIONS);
+                self.level_menu.reset_keys();
+                self.demo = None;
+            }
+            State::Playing => {
+                // Las acciones que ya estaban presionadas (como la pausa) no cuentan como pulsaciones nuevas
+                self.previous_input = InputFrame { actions: u16::MAX, ..InputFrame::default() };
+                self.mouse.reset();
+            }
+            // Al volver de la configuración se conservan el cuadro y la opción elegida
+            State::Paused if from == State::Playing => {
+                self.framebuffer.dim(PAUSE_DIM);
+                self.paused_frame = self.framebuffer.buffer.clone();
+                self.pause_menu = OptionsMenu::new(&PAUSE_OPTIONS);
+                pause_music();
+            }
+            State::Paused => {}
+            State::Victory => {
+                self.save_recording();
+                self.set_music(None);
+                play_victory_sound();
+                self.results = self.record_victory();
+            }
+            State::Defeat => {
+                self.save_recording();
+                self.set_music(None);
+            }
+            State::Settings => {
+                self.settings_return = from;
+                self.settings_menu = SettingsMenu::new(self.settings.clone());
+                self.controls = None;
+            }
+        }
+    }
+
+    // Lo que se puede cambiar sin reiniciar la partida; la resolución se aplica en resize
+    fn apply_settings(&mut self) {
+        self.mouse.sensitivity = self.settings.mouse_sensitivity;
+        self.mouse.invert = self.settings.invert_mouse;
+        self.mouse.smoothing = self.settings.mouse_smoothing;
+        set_volumes(self.settings.music_volume, self.settings.effects_volume);
+    }
+
+    // Vuelve a abrir la ventana con la resolución de la configuración.
+    // La terminal no se reabre: ya escala el framebuffer a su tamaño, y al cerrarla
+    // se saldría del modo crudo y de la pantalla alternativa.
+    fn resize(&mut self) {
+        let (width, height) = (self.settings.width, self.settings.height);
+        if !self.use_terminal {
+            // La ventana vieja se cierra antes de abrir la nueva
+            self.window = Box::new(ClosedBackend);
+            self.window = open_backend("BRAT MAZE", width, height, false);
+        }
+        self.framebuffer = Framebuffer::new(width, height);
+        self.mouse = MouseLook::new(width);
+        self.apply_settings();
+
+        // El cuadro detrás del menú de pausa se vuelve a dibujar con el tamaño nuevo
+        if let (State::Paused, Some(session)) = (self.settings_return, &mut self.session) {
+            session.draw(&mut self.framebuffer, 0.0, &self.settings);
+            self.framebuffer.dim(PAUSE_DIM);
+            self.paused_frame = self.framebuffer.buffer.clone();
+        }
+    }
+
+    // Solo cambia la música si es distinta de la que ya suena
+    fn set_music(&mut self, music: Option<&str>) {
+        if self.music.as_deref() == music {
+            return;
+        }
+        stop_music();
+        if let Some(music) = music {
+            play_background_music(music);
+        }
+        self.music = music.map(str::to_string);
+    }
+
+    // Devuelve false si el nivel no se pudo cargar
+    fn start(&mut self, level: Level, replay: Option<Recording>) -> bool {
+        match Session::new(level, replay) {
+            Ok(mut session) => {
+                if self.record_path.is_some() && !session.is_replay() {
+                    session.start_recording();
+                }
+                // Cada nivel declara su propia música en el encabezado
+                self.set_music(session.maze.meta.music.as_deref());
+                self.session = Some(session);
+                self.go(State::Playing);
+                true
+            }
+            Err(err) => {
+                eprintln!("{}", err);
+                false
+            }
+        }
+    }
+
+    fn restart(&mut self) {
+        let Some(session) = &self.session else {
+            return;
+        };
+        match session.restart() {
+            Ok(mut session) => {
+                if self.record_path.is_some() {
+                    session.start_recording();
+                }
+                self.set_music(session.maze.meta.music.as_deref());
+                self.session = Some(session);
+                self.go(State::Playing);
+            }
+            Err(err) => eprintln!("{}", err),
+        }
+    }
+
+    fn save_recording(&self) {
+        let recording = self.session.as_ref().and_then(|session| session.recording.as_ref());
+        let (Some(path), Some(recording)) = (&self.record_path, recording) else {
+            return;
+        };
+        if let Err(err) = recording.save(path) {
+            eprintln!("No se pudo guardar la grabación: {}", err);
+        }
+    }
+
+    // En el reto diario se guarda el tiempo para compararlo con el resto del equipo
+    fn record_victory(&self) -> Vec<DailyRecord> {
+        let Some(session) = &self.session else {
+            return Vec::new();
+        };
+        let Some(date) = session.daily else {
+            return Vec::new();
+        };
+        // Una repetición no es una partida nueva
+        if !session.is_replay() {
+            if let Err(err) = record_time(date, session.completion_time()) {
+                eprintln!("No se pudo guardar el tiempo del reto diario: {}", err);
+            }
+        }
+        times_for(date)
+    }
+
+    fn draw_fps(&mut self, x: i32, align: Align) {
+        let elapsed = self.last_frame_time.elapsed().as_secs_f32();
+        self.last_frame_time = Instant::now();
+        let fps = (1.0 / elapsed) as u32;
+        if !self.settings.show_fps {
+            return;
+        }
+        // Con la fuente incorporada y sombra, para que se lea sobre cualquier fondo
+        let text = format!("FPS: {}", fps);
+        let style = BitmapStyle { scale: 2, shadow: Some(0x000000), ..BitmapStyle::default() };
+        let left = match align {
+            Align::Right => x - bitmap_text_size(&text, style.scale).0 as i32,
+            _ => x,
+        };
+        draw_bitmap_text(&mut self.framebuffer, &text, left, 10, &style);
+    }
+
+    fn draw_background(&mut self, image: Option<&RgbaImage>) {
+        self.framebuffer.clear();
+        if let Some(image) = image {
+            draw_image(&mut self.framebuffer, image, 0, 0, 1.0, (0.0, 0.0));
+        }
+    }
+
+    fn update_menu(&mut self) -> Option<State> {
+        let image = self.menu_image.take();
+        self.draw_background(image.as_ref());
+        self.menu_image = image;
+
+        let chosen = self.main_menu.update(self.window.as_ref(), &mut self.framebuffer, OPTIONS_X, OPTIONS_Y);
+        if self.main_menu.pressed(Key::Escape) {
+            self.running = false;
+        }
+
+        match chosen {
+            Some(0) => Some(State::LevelSelect),
+            Some(1) => Some(State::Settings),
+            Some(_) => {
+                self.running = false;
+                None
+            }
+            None => None,
+        }
+    }
+
+    fn update_level_select(&mut self, frame_time: f32) -> Option<State> {
+        // Durante la demostración el menú no se dibuja; la primera tecla o movimiento del mouse solo vuelve a él
+        if self.demo.is_some() {
+            self.level_menu.watch(self.window.as_ref());
+            if self.level_menu.idle() < ATTRACT_DELAY {
+                self.demo = None;
+            }
+        } else {
+            let image = self.menu_image.take();
+            self.draw_background(image.as_ref());
+            self.menu_image = image;
+
+            if let Some(index) = self.level_menu.update(self.window.as_ref(), &mut self.framebuffer) {
+                // Se juega el laberinto aleatorio de la vista previa; la próxima vez será otro
+                let entry = &self.level_menu.levels[index];
+                let mut level = Level::from_path(&entry.path);
+                level.random = entry.random;
+                if level.random {
+                    self.level_menu.reroll(index);
+                }
+                self.start(level, None);
+                return None;
+            }
+            if self.level_menu.pressed(Key::Escape) {
+                return Some(State::Menu);
+            }
+        }
+
+        if self.level_menu.idle() >= ATTRACT_DELAY && !self.demo.as_mut().is_some_and(|demo| demo.update(frame_time)) {
+            self.demo = Level::random().maze.ok().and_then(Demo::new);
+        }
+
+        if let Some(demo) = &self.demo {
+            self.framebuffer.clear();
+            let mut view = demo.player.clone();
+            view.fov = self.settings.fov_radians();
+            render3d(&mut self.framebuffer, &view, &demo.maze, &self.settings.render_options());
+        }
+
+        // FPS en la esquina superior derecha
+        let fps_x = self.framebuffer.width as i32 - 10;
+        self.draw_fps(fps_x, Align::Right);
+        None
+    }
+
+    fn update_playing(&mut self, frame_time: f32) -> Option<State> {
+        let Some(session) = &mut self.session else {
+            return Some(State::LevelSelect);
+        };
+
+        let input = InputFrame::capture(self.window.as_ref(), &self.bindings, &mut self.mouse, frame_time);
+        let just_pressed = |action| input.is_down(action) && !self.previous_input.is_down(action);
+        let (pause_pressed, controls_pressed) = (just_pressed(Action::Pause), just_pressed(Action::Controls));
+        self.previous_input = input;
+
+        if pause_pressed {
+            return Some(State::Paused);
+        }
+        if controls_pressed {
+            return Some(State::Settings);
+        }
+
+        let outcome = session.update(input, frame_time);
+        session.draw(&mut self.framebuffer, frame_time, &self.settings);
+        self.draw_fps(10, Align::Left);
+
+        match outcome {
+            Some(Outcome::Won) => Some(State::Victory),
+            Some(Outcome::OutOfTime) => Some(State::Defeat),
+            Some(Outcome::ReplayEnded) => Some(State::Menu),
+            None => None,
+        }
+    }
+
+    // La partida queda detenida: sin pasos de simulación no avanzan el tiempo del nivel ni el screamer
+    fn update_paused(&mut self) -> Option<State> {
+        self.framebuffer.buffer.copy_from_slice(&self.paused_frame);
+        let chosen = self.pause_menu.update(self.window.as_ref(), &mut self.framebuffer, OPTIONS_X, OPTIONS_Y);
+        let resume = self.bindings.keys(Action::Pause).iter().any(|key| self.pause_menu.pressed(*key));
+
+        match chosen {
+            _ if resume => Some(State::Playing),
+            Some(0) => Some(State::Playing),
+            Some(1) => {
+                self.restart();
+                None
+            }
+            Some(2) => Some(State::Settings),
+            Some(_) => Some(State::Menu),
+            None => None,
+        }
+    }
+
+    fn update_victory(&mut self) -> Option<State> {
+        let image = self.victory_image.take();
+        self.draw_background(image.as_ref());
+        self.victory_image = image;
+
+        if let Some(session) = &self.session {
+            let text = format!("Tiempo: {:.2} s", session.completion_time());
+            draw_string(&mut self.framebuffer, &text, 10, 10, &TextStyle { size: 36.0, ..TextStyle::default() });
+        }
+        for (i, record) in self.results.iter().take(10).enumerate() {
+            let text = format!("{}. {:.2} {}", i + 1, record.seconds, record.player);
+            draw_string(&mut self.framebuffer, &text, 10, 60 + i as i32 * 24, &TextStyle::default());
+        }
+
+        // Enter vuelve a la lista de niveles, R repite el nivel y Escape va al menú principal
+        let window = self.window.as_ref();
+        if self.keys.pressed(window, Key::Enter) {
+            return Some(State::LevelSelect);
+        }
+        if self.keys.pressed(window, Key::R) {
+            self.restart();
+            return None;
+        }
+        if self.keys.pressed(window, Key::Escape) {
+            return Some(State::Menu);
+        }
+        None
+    }
+
+    fn update_defeat(&mut self) -> Option<State> {
+        self.framebuffer.set_background_color(0x400000);
+        self.framebuffer.clear();
+        self.framebuffer.set_background_color(0x000000);
+        let (x, y) = (self.framebuffer.width as i32 / 2, self.framebuffer.height as i32 / 2);
+        draw_string(&mut self.framebuffer, "¡Sin tiempo!", x, y - 60, &TextStyle { size: 48.0, align: Align::Center, ..TextStyle::default() });
+        let hint = "Enter: intentar de nuevo    Escape: elegir otro nivel";
+        draw_string(&mut self.framebuffer, hint, x, y + 10, &TextStyle { size: 20.0, align: Align::Center, ..TextStyle::default() });
+
+        // Enter o R intentan de nuevo; Escape vuelve a la lista de niveles
+        let window = self.window.as_ref();
+        if self.keys.pressed(window, Key::Enter) | self.keys.pressed(window, Key::R) {
+            self.restart();
+            return None;
+        }
+        if self.keys.pressed(window, Key::Escape) {
+            return Some(State::LevelSelect);
+        }
+        None
+    }
+
+    fn update_settings(&mut self) -> Option<State> {
+        match self.settings_return {
+            State::Playing => {}
+            State::Paused => self.framebuffer.buffer.copy_from_slice(&self.paused_frame),
+            _ => {
+                let image = self.menu_image.take();
+                self.draw_background(image.as_ref());
+                self.menu_image = image;
+            }
+        }
+
+        let window = self.window.as_ref();
+        if let Some(controls) = &mut self.controls {
+            if !controls.update(window, &mut self.framebuffer) {
+                self.bindings = controls.bindings.clone();
+                if let Err(err) = self.bindings.save() {
+                    eprintln!("No se pudieron guardar los controles: {}", err);
+                }
+                self.controls = None;
+                self.settings_menu.reset_keys();
+            }
+        }
+        // Al cerrar los controles la configuración se dibuja en el mismo cuadro
+        let event = match self.controls {
+            Some(_) => None,
+            None => self.settings_menu.update(window, &mut self.framebuffer),
+        };
+
+        // Los cambios se notan enseguida, salvo la resolución, que espera a cerrar la pantalla
+        if self.settings_menu.settings != self.settings {
+            self.settings = self.settings_menu.settings.clone();
+            self.apply_settings();
+        }
+
+        match event {
+            Some(SettingsEvent::Controls) => {
+                self.controls = Some(ControlsMenu::new(self.bindings.clone()));
+                None
+            }
+            Some(SettingsEvent::Close) => {
+                if let Err(err) = self.settings.save() {
+                    eprintln!("No se pudo guardar la configuración: {}", err);
+                }
+                if (self.framebuffer.width, self.framebuffer.height) != (self.settings.width, self.settings.height) {
+                    self.resize();
+                }
+                Some(self.settings_return)
+            }
+            None => None,
+        }
+    }
+}
+
+// Repite una grabación sin pantalla, tan rápido como se pueda. Devuelve cómo
+// terminó la partida y su tiempo final.
+pub fn run_headless(recording: Recording) -> Result<(Outcome, f32), MazeError> {
+    let mut session = Session::new(Level::from_path(&recording.level), Some(recording))?;
+    loop {
+        if let Some(outcome) = session.update(InputFrame::default(), TICK) {
+            return Ok((outcome, session.completion_time()));
+        }
+    }
+}
@@ -0,0 +1,338 @@
+use std::collections::BTreeMap;
+use std::fmt::Write;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use crate::maze::{Maze, GOAL};
+
+// Generador propio (SplitMix64) para que una semilla produzca siempre el mismo
+// laberinto, sin depender de la versión de ninguna biblioteca externa.
+pub struct Rng(u64);
+
+impl Rng {
+    pub fn new(seed: u64) -> Self {
+        Rng(seed)
+    }
+
+    pub fn next_u64(&mut self) -> u64 {
+        self.0 = self.0.wrapping_add(0x9E37_79B9_7F4A_7C15);
+        let mut z = self.0;
+        z = (z ^ (z >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+        z ^ (z >> 31)
+    }
+
+    // Entero en 0..n
+    pub fn below(&mut self, n: usize) -> usize {
+        (self.next_u64() % n as u64) as usize
+    }
+
+    pub fn chance(&mut self) -> bool {
+        self.next_u64() & 1 == 1
+    }
+
+    pub fn shuffle<T>(&mut self, items: &mut [T]) {
+        for i in (1..items.len()).rev() {
+            items.swap(i, self.below(i + 1));
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Algorithm {
+    RecursiveBacktracker,
+    Prim,
+    Kruskal,
+    Eller,
+    Wilson,
+}
+
+impl Algorithm {
+    pub const ALL: [Algorithm; 5] = [
+        Algorithm::RecursiveBacktracker,
+        Algorithm::Prim,
+        Algorithm::Kruskal,
+        Algorithm::Eller,
+        Algorithm::Wilson,
+    ];
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            Algorithm::RecursiveBacktracker => "backtracker",
+            Algorithm::Prim => "prim",
+            Algorithm::Kruskal => "kruskal",
+            Algorithm::Eller => "eller",
+            Algorithm::Wilson => "wilson",
+        }
+    }
+
+    pub fn from_name(name: &str) -> Option<Algorithm> {
+        Algorithm::ALL.into_iter().find(|algorithm| algorithm.name() == name)
+    }
+}
+
+// Laberinto perfecto de width x height celdas; cada celda recuerda si está
+// abierta hacia la derecha y hacia abajo.
+pub struct GeneratedMaze {
+    pub width: usize,
+    pub height: usize,
+    pub algorithm: Algorithm,
+    pub seed: u64,
+    open_right: Vec<bool>,
+    open_down: Vec<bool>,
+}
+
+impl GeneratedMaze {
+    fn new(width: usize, height: usize, algorithm: Algorithm, seed: u64) -> Self {
+        GeneratedMaze {
+            width,
+            height,
+            algorithm,
+            seed,
+            open_right: vec![false; width * height],
+            open_down: vec![false; width * height],
+        }
+    }
+
+    fn neighbors(&self, cell: usize) -> Vec<usize> {
+        let (col, row) = (cell % self.width, cell / self.width);
+        let mut neighbors = Vec::with_capacity(4);
+        if row > 0 {
+            neighbors.push(cell - self.width);
+        }
+        if col + 1 < self.width {
+            neighbors.push(cell + 1);
+        }
+        if row + 1 < self.height {
+            neighbors.push(cell + self.width);
+        }
+        if col > 0 {
+            neighbors.push(cell - 1);
+        }
+        neighbors
+    }
+
+    // Quita la pared entre dos celdas vecinas
+    fn carve(&mut self, a: usize, b: usize) {
+        let (low, high) = (a.min(b), a.max(b));
+        if high == low + 1 {
+            self.open_right[low] = true;
+        } else {
+            self.open_down[low] = true;
+        }
+    }
+
+    pub fn is_open_right(&self, col: usize, row: usize) -> bool {
+        self.open_right[row * self.width + col]
+    }
+
+    pub fn is_open_down(&self, col: usize, row: usize) -> bool {
+        self.open_down[row * self.width + col]
+    }
+
+    // Mismo formato que los laberintos dibujados a mano: "+--+" y "|  |",
+    // con la aparición arriba a la izquierda y la meta abajo a la derecha.
+    pub fn to_text(&self) -> String {
+        let mut text = String::new();
+        writeln!(text, "title: Laberinto aleatorio {} ({})", self.seed, self.algorithm.name()).unwrap();
+        writeln!(text, "---").unwrap();
+
+        let spawn = if self.is_open_right(0, 0) { '>' } else { 'v' };
+
+        for row in 0..self.height {
+            let mut top = String::from("+");
+            let mut middle = String::from("|");
+            for col in 0..self.width {
+                let open_up = row > 0 && self.is_open_down(col, row - 1);
+                top.push_str(if open_up { "  +" } else { "--+" });
+
+                let interior = match (col, row) {
+                    (0, 0) => format!("{} ", spawn),
+                    _ if col == self.width - 1 && row == self.height - 1 => format!(" {}", GOAL),
+                    _ => "  ".to_string(),
+                };
+                middle.push_str(&interior);
+                middle.push(if self.is_open_right(col, row) { ' ' } else { '|' });
+            }
+            writeln!(text, "{}", top).unwrap();
+            writeln!(text, "{}", middle).unwrap();
+        }
+
+        let bottom = "--+".repeat(self.width);
+        writeln!(text, "+{}", bottom).unwrap();
+        text
+    }
+
+    pub fn to_maze(&self) -> Maze {
+        Maze::parse(&self.to_text()).expect("el generador siempre produce laberintos válidos")
+    }
+}
+
+pub const DEFAULT_WIDTH: usize = 8;
+pub const DEFAULT_HEIGHT: usize = 6;
+
+// Semilla distinta en cada partida para la opción "laberinto aleatorio"
+pub fn seed_from_clock() -> u64 {
+    SystemTime::now().duration_since(UNIX_EPOCH).map_or(0, |elapsed| elapsed.as_nanos() as u64)
+}
+
+// Tamaño mínimo de 2x2 celdas para que la aparición y la meta no compartan celda
+pub fn generate(algorithm: Algorithm, width: usize, height: usize, seed: u64) -> GeneratedMaze {
+    let mut maze = GeneratedMaze::new(width.max(2), height.max(2), algorithm, seed);
+    let mut rng = Rng::new(seed);
+
+    match algorithm {
+        Algorithm::RecursiveBacktracker => recursive_backtracker(&mut maze, &mut rng),
+        Algorithm::Prim => prim(&mut maze, &mut rng),
+        Algorithm::Kruskal => kruskal(&mut maze, &mut rng),
+        Algorithm::Eller => eller(&mut maze, &mut rng),
+        Algorithm::Wilson => wilson(&mut maze, &mut rng),
+    }
+
+    maze
+}
+
+fn recursive_backtracker(maze: &mut GeneratedMaze, rng: &mut Rng) {
+    let mut visited = vec![false; maze.width * maze.height];
+    let start = rng.below(visited.len());
+    let mut stack = vec![start];
+    visited[start] = true;
+
+    while let Some(&cell) = stack.last() {
+        let unvisited: Vec<usize> = maze.neighbors(cell).into_iter().filter(|&n| !visited[n]).collect();
+        if unvisited.is_empty() {
+            stack.pop();
+            continue;
+        }
+        let next = unvisited[rng.below(unvisited.len())];
+        maze.carve(cell, next);
+        visited[next] = true;
+        stack.push(next);
+    }
+}
+
+fn prim(maze: &mut GeneratedMaze, rng: &mut Rng) {
+    let mut in_maze = vec![false; maze.width * maze.height];
+    let start = rng.below(in_maze.len());
+    in_maze[start] = true;
+
+    let mut frontier: Vec<(usize, usize)> = maze.neighbors(start).into_iter().map(|n| (start, n)).collect();
+    while !frontier.is_empty() {
+        let (from, to) = frontier.swap_remove(rng.below(frontier.len()));
+        if in_maze[to] {
+            continue;
+        }
+        maze.carve(from, to);
+        in_maze[to] = true;
+        frontier.extend(maze.neighbors(to).into_iter().filter(|&n| !in_maze[n]).map(|n| (to, n)));
+    }
+}
+
+fn kruskal(maze: &mut GeneratedMaze, rng: &mut Rng) {
+    fn find(parent: &mut [usize], cell: usize) -> usize {
+        let mut root = cell;
+        while parent[root] != root {
+            root = parent[root];
+        }
+        let mut current = cell;
+        while parent[current] != root {
+            let next = parent[current];
+            parent[current] = root;
+            current = next;
+        }
+        root
+    }
+
+    let mut parent: Vec<usize> = (0..maze.width * maze.height).collect();
+    let mut walls = Vec::new();
+    for cell in 0..parent.len() {
+        if cell % maze.width + 1 < maze.width {
+            walls.push((cell, cell + 1));
+        }
+        if cell / maze.width + 1 < maze.height {
+            walls.push((cell, cell + maze.width));
+        }
+    }
+    rng.shuffle(&mut walls);
+
+    for (a, b) in walls {
+        let (root_a, root_b) = (find(&mut parent, a), find(&mut parent, b));
+        if root_a != root_b {
+            parent[root_a] = root_b;
+            maze.carve(a, b);
+        }
+    }
+}
+
+fn eller(maze: &mut GeneratedMaze, rng: &mut Rng) {
+    let width = maze.width;
+    let mut sets = vec![0; width];
+    let mut next_set = 1;
+
+    for row in 0..maze.height {
+        let last_row = row == maze.height - 1;
+        let base = row * width;
+
+        for set in sets.iter_mut().filter(|set| **set == 0) {
+            *set = next_set;
+            next_set += 1;
+        }
+
+        // Unir celdas vecinas de conjuntos distintos (en la última fila, todas)
+        for col in 0..width - 1 {
+            if sets[col] != sets[col + 1] && (last_row || rng.chance()) {
+                maze.carve(base + col, base + col + 1);
+                let (old, new) = (sets[col + 1], sets[col]);
+                for set in sets.iter_mut().filter(|set| **set == old) {
+                    *set = new;
+                }
+            }
+        }
+
+        if last_row {
+            break;
+        }
+
+        // Cada conjunto baja al menos una vez a la fila siguiente
+        let mut members: BTreeMap<usize, Vec<usize>> = BTreeMap::new();
+        for (col, &set) in sets.iter().enumerate() {
+            members.entry(set).or_default().push(col);
+        }
+
+        let mut next_sets = vec![0; width];
+        for (set, mut cols) in members {
+            rng.shuffle(&mut cols);
+            for (i, col) in cols.into_iter().enumerate() {
+                if i == 0 || rng.chance() {
+                    maze.carve(base + col, base + col + width);
+                    next_sets[col] = set;
+                }
+            }
+        }
+        sets = next_sets;
+    }
+}
+
+fn wilson(maze: &mut GeneratedMaze, rng: &mut Rng) {
+    let cells = maze.width * maze.height;
+    let mut in_maze = vec![false; cells];
+    in_maze[rng.below(cells)] = true;
+    let mut next = vec![0; cells];
+
+    for start in 0..cells {
+        // Caminata aleatoria hasta tocar el laberinto; al sobrescribir `next`
+        // los ciclos de la caminata se borran solos.
+        let mut cell = start;
+        while !in_maze[cell] {
+            let neighbors = maze.neighbors(cell);
+            next[cell] = neighbors[rng.below(neighbors.len())];
+            cell = next[cell];
+        }
+
+        let mut cell = start;
+        while !in_maze[cell] {
+            maze.carve(cell, next[cell]);
+            in_maze[cell] = true;
+            cell = next[cell];
+        }
+    }
+}
@@ -0,0 +1,309 @@
+use std::collections::HashSet;
+use std::fs;
+use std::io;
+use std::path::PathBuf;
+
+use minifb::Key;
+
+use crate::backend::Backend;
+
+// Archivos de configuración en la carpeta del usuario (~/.config/brat_maze en Linux)
+pub const CONFIG_DIR_NAME: &str = "brat_maze";
+pub const BINDINGS_FILE: &str = "controls.txt";
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Action {
+    MoveForward,
+    MoveBackward,
+    StrafeLeft,
+    StrafeRight,
+    TurnLeft,
+    TurnRight,
+    ToggleMap,
+    Hint,
+    Pause,
+    Controls,
+}
+
+impl Action {
+    pub const ALL: [Action; 10] = [
+        Action::MoveForward,
+        Action::MoveBackward,
+        Action::StrafeLeft,
+        Action::StrafeRight,
+        Action::TurnLeft,
+        Action::TurnRight,
+        Action::ToggleMap,
+        Action::Hint,
+        Action::Pause,
+        Action::Controls,
+    ];
+
+    // Nombre en el archivo de controles
+    pub fn name(&self) -> &'static str {
+        match self {
+            Action::MoveForward => "move_forward",
+            Action::MoveBackward => "move_backward",
+            Action::StrafeLeft => "strafe_left",
+            Action::StrafeRight => "strafe_right",
+            Action::TurnLeft => "turn_left",
+            Action::TurnRight => "turn_right",
+            Action::ToggleMap => "toggle_map",
+            Action::Hint => "hint",
+            Action::Pause => "pause",
+            Action::Controls => "controls",
+        }
+    }
+
+    // Nombre en la pantalla de controles
+    pub fn label(&self) -> &'static str {
+        match self {
+            Action::MoveForward => "Avanzar",
+            Action::MoveBackward => "Retroceder",
+            Action::StrafeLeft => "Paso a la izquierda",
+            Action::StrafeRight => "Paso a la derecha",
+            Action::TurnLeft => "Girar a la izquierda",
+            Action::TurnRight => "Girar a la derecha",
+            Action::ToggleMap => "Cambiar vista 2D/3D",
+            Action::Hint => "Pista",
+            Action::Pause => "Pausa",
+            Action::Controls => "Configuración",
+        }
+    }
+
+    pub fn from_name(name: &str) -> Option<Action> {
+        Action::ALL.into_iter().find(|action| action.name() == name)
+    }
+
+    // ALL sigue el orden de la declaración
+    pub fn index(&self) -> usize {
+        *self as usize
+    }
+
+    fn default_keys(&self) -> Vec<Key> {
+        match self {
+            Action::MoveForward => vec![Key::W, Key::Up],
+            Action::MoveBackward => vec![Key::S, Key::Down],
+            Action::StrafeLeft => vec![Key::A],
+            Action::StrafeRight => vec![Key::D],
+            Action::TurnLeft => vec![Key::Left],
+            Action::TurnRight => vec![Key::Right],
+            Action::ToggleMap => vec![Key::M],
+            Action::Hint => vec![Key::H],
+            Action::Pause => vec![Key::Escape],
+            Action::Controls => vec![Key::F1],
+        }
+    }
+}
+
+// Teclas que se pueden asignar; el nombre en el archivo es el de minifb ("W", "Up", "Key1"...)
+pub const BINDABLE_KEYS: [Key; 66] = [
+    Key::A, Key::B, Key::C, Key::D, Key::E, Key::F, Key::G, Key::H, Key::I, Key::J, Key::K, Key::L, Key::M,
+    Key::N, Key::O, Key::P, Key::Q, Key::R, Key::S, Key::T, Key::U, Key::V, Key::W, Key::X, Key::Y, Key::Z,
+    Key::Key0, Key::Key1, Key::Key2, Key::Key3, Key::Key4, Key::Key5, Key::Key6, Key::Key7, Key::Key8, Key::Key9,
+    Key::F1, Key::F2, Key::F3, Key::F4, Key::F5, Key::F6, Key::F7, Key::F8, Key::F9, Key::F10, Key::F11, Key::F12,
+    Key::Up, Key::Down, Key::Left, Key::Right,
+    Key::Space, Key::Enter, Key::Escape, Key::Tab, Key::Backspace,
+    Key::LeftShift, Key::RightShift, Key::LeftCtrl, Key::RightCtrl, Key::LeftAlt, Key::RightAlt,
+    Key::PageUp, Key::PageDown, Key::Home,
+];
+
+pub fn key_name(key: Key) -> String {
+    format!("{:?}", key)
+}
+
+pub fn key_from_name(name: &str) -> Option<Key> {
+    BINDABLE_KEYS.into_iter().find(|key| key_name(*key).eq_ignore_ascii_case(name))
+}
+
+// Detecta el cuadro en que se presiona una tecla, no mientras se mantiene
+pub struct KeyEdges {
+    held: HashSet<Key>,
+}
+
+impl Default for KeyEdges {
+    fn default() -> Self {
+        let mut edges = KeyEdges { held: HashSet::new() };
+        edges.block();
+        edges
+    }
+}
+
+impl KeyEdges {
+    pub fn pressed(&mut self, window: &dyn Backend, key: Key) -> bool {
+        if window.is_key_down(key) {
+            self.held.insert(key)
+        } else {
+            self.held.remove(&key);
+            false
+        }
+    }
+
+    // Las teclas que ya están presionadas no cuentan hasta que se suelten, por
+    // ejemplo el Enter que abrió la pantalla
+    pub fn block(&mut self) {
+        self.held.extend(BINDABLE_KEYS);
+    }
+}
+
+pub const DEFAULT_MOUSE_SENSITIVITY: f32 = 0.005; // Radianes por píxel
+
+// Si el backend no puede devolver el cursor al centro (por ejemplo en Wayland), con el
+// cursor en el borde de la ventana se sigue girando a esta velocidad
+const EDGE_WIDTH: f32 = 0.02; // Fracción del ancho de la ventana
+const EDGE_TURN_SPEED: f32 = 2.0; // Radianes por segundo
+
+// Estado del mouse para mirar alrededor: guarda la última posición leída para
+// convertirla en movimiento relativo y, donde se puede, devuelve el cursor al centro.
+pub struct MouseLook {
+    pub sensitivity: f32,
+    pub invert: bool,
+    pub smoothing: f32, // 0 sin suavizado; cerca de 1 muy suave
+    width: f32,
+    last_x: Option<f32>,
+    smoothed: f32,
+}
+
+impl MouseLook {
+    pub fn new(width: usize) -> Self {
+        MouseLook { sensitivity: DEFAULT_MOUSE_SENSITIVITY, invert: false, smoothing: 0.0, width: width as f32, last_x: None, smoothed: 0.0 }
+    }
+
+    // Olvida la última posición, por ejemplo al volver de un menú, para que el giro no salte
+    pub fn reset(&mut self) {
+        self.last_x = None;
+        self.smoothed = 0.0;
+    }
+
+    // Giro en radianes desde la lectura anterior; `dt` son los segundos del cuadro
+    pub fn turn(&mut self, window: &dyn Backend, dt: f32) -> f32 {
+        // Fuera de la ventana no hay lectura: al volver a entrar no se cuenta el salto
+        let Some((mouse_x, mouse_y)) = window.get_mouse_pos() else {
+            self.reset();
+            return 0.0;
+        };
+
+        let mut raw = match self.last_x.replace(mouse_x) {
+            Some(last_x) => (mouse_x - last_x) * self.sensitivity,
+            None => 0.0,
+        };
+        // Con el cursor de vuelta en el centro el giro no tiene límite
+        let center = (self.width / 2.0).floor();
+        let edge = self.width * EDGE_WIDTH;
+        if mouse_x != center && window.warp_mouse(center, mouse_y) {
+            self.last_x = Some(center);
+        } else if mouse_x <= edge {
+            raw -= EDGE_TURN_SPEED * dt;
+        } else if mouse_x >= self.width - 1.0 - edge {
+            raw += EDGE_TURN_SPEED * dt;
+        }
+
+        let smoothing = self.smoothing.clamp(0.0, 0.95);
+        self.smoothed = self.smoothed * smoothing + raw * (1.0 - smoothing);
+        if self.invert { -self.smoothed } else { self.smoothed }
+    }
+}
+
+pub fn config_dir() -> Option<PathBuf> {
+    dirs::config_dir().map(|dir| dir.join(CONFIG_DIR_NAME))
+}
+
+// Teclas asignadas a cada acción; una acción puede tener varias
+#[derive(Clone, Debug, PartialEq)]
+pub struct Bindings {
+    keys: Vec<Vec<Key>>, // Indexado por Action::index
+}
+
+impl Default for Bindings {
+    fn default() -> Self {
+        Bindings { keys: Action::ALL.iter().map(Action::default_keys).collect() }
+    }
+}
+
+impl Bindings {
+    pub fn keys(&self, action: Action) -> &[Key] {
+        &self.keys[action.index()]
+    }
+
+    pub fn is_down(&self, window: &dyn Backend, action: Action) -> bool {
+        self.keys(action).iter().any(|key| window.is_key_down(*key))
+    }
+
+    // Agrega una tecla a la acción y se la quita a cualquier otra que la tuviera
+    pub fn bind(&mut self, action: Action, key: Key) {
+        for keys in &mut self.keys {
+            keys.retain(|bound| *bound != key);
+        }
+        self.keys[action.index()].push(key);
+    }
+
+    pub fn clear(&mut self, action: Action) {
+        self.keys[action.index()].clear();
+    }
+
+    // Líneas "acción: tecla, tecla"; las acciones que no aparecen conservan sus teclas por defecto.
+    // Devuelve también los problemas encontrados, uno por línea ignorada.
+    pub fn parse(text: &str) -> (Bindings, Vec<String>) {
+        let mut bindings = Bindings::default();
+        let mut problems = Vec::new();
+
+        for (index, raw) in text.lines().enumerate() {
+            let line = index + 1;
+            let raw = raw.trim();
+            if raw.is_empty() || raw.starts_with('#') {
+                continue;
+            }
+
+            let Some((name, value)) = raw.split_once(':') else {
+                problems.push(format!("línea {}: se esperaba \"acción: teclas\"", line));
+                continue;
+            };
+            let Some(action) = Action::from_name(name.trim()) else {
+                problems.push(format!("línea {}: acción desconocida {:?}", line, name.trim()));
+                continue;
+            };
+
+            let mut keys = Vec::new();
+            for name in value.split(',').map(str::trim).filter(|name| !name.is_empty()) {
+                match key_from_name(name) {
+                    Some(key) => keys.push(key),
+                    None => problems.push(format!("línea {}: tecla desconocida {:?}", line, name)),
+                }
+            }
+            bindings.keys[action.index()] = keys;
+        }
+
+        (bindings, problems)
+    }
+
+    pub fn to_text(&self) -> String {
+        let mut text = String::from("# acción: teclas separadas por comas\n");
+        for action in Action::ALL {
+            let keys: Vec<String> = self.keys(action).iter().map(|key| key_name(*key)).collect();
+            text.push_str(&format!("{}: {}\n", action.name(), keys.join(", ")));
+        }
+        text
+    }
+
+    // Sin archivo se usan los controles por defecto
+    pub fn load() -> Bindings {
+        let Some(path) = config_dir().map(|dir| dir.join(BINDINGS_FILE)) else {
+            return Bindings::default();
+        };
+        let Ok(text) = fs::read_to_string(&path) else {
+            return Bindings::default();
+        };
+
+        let (bindings, problems) = Bindings::parse(&text);
+        for problem in problems {
+            eprintln!("{}: {}", path.display(), problem);
+        }
+        bindings
+    }
+
+    pub fn save(&self) -> io::Result<()> {
+        let dir = config_dir().ok_or_else(|| io::Error::new(io::ErrorKind::NotFound, "no hay carpeta de configuración"))?;
+        fs::create_dir_all(&dir)?;
+        fs::write(dir.join(BINDINGS_FILE), self.to_text())
+    }
+}
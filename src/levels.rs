@@ -0,0 +1,107 @@
+use std::fs;
+use std::path::Path;
+
+use crate::daily::{Date, DAILY_HEIGHT, DAILY_WIDTH};
+use crate::generator::{generate, seed_from_clock, Algorithm, DEFAULT_HEIGHT, DEFAULT_WIDTH};
+use crate::maze::{load_maze, Maze, MazeError};
+
+pub const LEVELS_DIR: &str = "mazes";
+
+// Si existe, el manifiesto lista los niveles del paquete en orden (uno por línea)
+pub const PACK_MANIFEST: &str = "pack.txt";
+
+pub struct Level {
+    pub path: String,
+    pub title: String,
+    pub maze: Result<Maze, MazeError>,
+    pub daily: Option<Date>, // Fecha del reto, si es el reto diario
+    pub random: bool, // El laberinto aleatorio del menú: cada vez que se juega cambia la semilla
+}
+
+impl Level {
+    pub fn load(path: &str) -> Level {
+        let maze = load_maze(path);
+
+        // Sin título en el encabezado se usa el nombre del archivo
+        let title = maze.as_ref().ok().and_then(|maze| maze.meta.title.clone()).unwrap_or_else(|| {
+            Path::new(path).file_stem().map_or_else(|| path.to_string(), |stem| stem.to_string_lossy().into_owned())
+        });
+
+        Level { path: path.to_string(), title, maze, daily: None, random: false }
+    }
+
+    pub fn generated(algorithm: Algorithm, width: usize, height: usize, seed: u64) -> Level {
+        let maze = generate(algorithm, width, height, seed).to_maze();
+        Level {
+            path: format!("{}:{}", algorithm.name(), seed),
+            title: maze.meta.title.clone().unwrap_or_default(),
+            maze: Ok(maze),
+            daily: None,
+            random: false,
+        }
+    }
+
+    // Laberinto nuevo con semilla y algoritmo elegidos al azar
+    pub fn random() -> Level {
+        let seed = seed_from_clock();
+        let algorithm = Algorithm::ALL[(seed % Algorithm::ALL.len() as u64) as usize];
+        let mut level = Level::generated(algorithm, DEFAULT_WIDTH, DEFAULT_HEIGHT, seed);
+        level.random = true;
+        level
+    }
+
+    // El mismo laberinto para todos durante el día
+    pub fn daily(date: Date) -> Level {
+        let mut level = Level::generated(date.algorithm(), DAILY_WIDTH, DAILY_HEIGHT, date.seed());
+        level.path = format!("daily:{}", date);
+        level.title = format!("Reto diario {}", date);
+        if let Ok(maze) = &mut level.maze {
+            maze.meta.title = Some(level.title.clone());
+        }
+        level.daily = Some(date);
+        level
+    }
+
+    // Inverso de `path`: un archivo, "algoritmo:semilla" o "daily:AAAA-MM-DD"
+    pub fn from_path(path: &str) -> Level {
+        if !Path::new(path).exists() {
+            if let Some(date) = path.strip_prefix("daily:").and_then(Date::parse) {
+                return Level::daily(date);
+            }
+            if let Some((name, seed)) = path.split_once(':') {
+                if let (Some(algorithm), Ok(seed)) = (Algorithm::from_name(name), seed.parse()) {
+                    return Level::generated(algorithm, DEFAULT_WIDTH, DEFAULT_HEIGHT, seed);
+                }
+            }
+        }
+        Level::load(path)
+    }
+}
+
+pub fn discover_levels(dir: &str) -> Vec<Level> {
+    let dir = Path::new(dir);
+    let manifest = dir.join(PACK_MANIFEST);
+
+    let mut paths: Vec<String> = if let Ok(text) = fs::read_to_string(&manifest) {
+        text.lines()
+            .map(str::trim)
+            .filter(|line| !line.is_empty() && !line.starts_with('#'))
+            .map(|line| dir.join(line).to_string_lossy().into_owned())
+            .collect()
+    } else {
+        let Ok(entries) = fs::read_dir(dir) else {
+            return Vec::new();
+        };
+        let mut paths: Vec<String> = entries
+            .filter_map(|entry| entry.ok())
+            .map(|entry| entry.path())
+            .filter(|path| path.is_file() && path.extension().is_some_and(|ext| ext == "txt"))
+            .map(|path| path.to_string_lossy().into_owned())
+            .collect();
+        paths.sort();
+        paths
+    };
+
+    paths.dedup();
+    paths.iter().map(|path| Level::load(path)).collect()
+}
@@ -0,0 +1,24 @@
+pub mod backend;
+pub mod bitmap_font;
+pub mod bot;
+pub mod cli;
+pub mod daily;
+pub mod framebuffer;
+pub mod game;
+pub mod generator;
+pub mod input;
+pub mod levels;
+pub mod maze;
+pub mod menu;
+pub mod player;
+pub mod ray_casting;
+pub mod render;
+pub mod replay;
+pub mod session;
+pub mod settings;
+pub mod solver;
+pub mod sounds;
+pub mod terminal;
+pub mod text;
+pub mod texture;
+pub mod ui;
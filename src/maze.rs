@@ -1,21 +1,428 @@
-use std::fs::File;
-use std::io::{BufRead, BufReader};
+use std::f32::consts::PI;
+use std::fmt;
+use std::fs;
+use nalgebra_glm::Vec2;
 
-pub fn load_maze(filename: &str) -> Vec<Vec<char>> {
-    let file = File::open(filename).expect(&format!("No se pudo abrir el archivo de laberinto: {}", filename));
-    let reader = BufReader::new(file);
+use crate::texture::Texture;
 
-    reader
-        .lines()
-        .map(|line| line.unwrap().chars().collect())
-        .collect()
+// Tamaño en unidades del mundo de cada celda del laberinto
+pub const DEFAULT_BLOCK_SIZE: usize = 100;
+
+pub const GOAL: char = 'g';
+const WALLS: [char; 3] = ['+', '-', '|'];
+
+// Marcador de aparición sin orientación; el jugador mira hacia donde miraba siempre
+pub const SPAWN: char = 'S';
+pub const DEFAULT_FACING: f32 = PI / 1.8;
+
+// Orientación de los marcadores '^ > v <' (el eje y crece hacia abajo)
+fn spawn_facing(cell: char) -> Option<f32> {
+    match cell {
+        SPAWN => Some(DEFAULT_FACING),
+        '>' => Some(0.0),
+        'v' => Some(PI / 2.0),
+        '<' => Some(PI),
+        '^' => Some(3.0 * PI / 2.0),
+        _ => None,
+    }
+}
+
+// Separa el encabezado de metadatos del dibujo del laberinto
+pub const HEADER_END: &str = "---";
+
+pub const DEFAULT_SKY_COLOR: u32 = 0x87CEEB;
+pub const DEFAULT_FLOOR_COLOR: u32 = 0x006400;
+pub const DEFAULT_WALL_TEXTURE: &str = "src/assets/images/green_texture.jpg";
+pub const DEFAULT_GOAL_TEXTURE: &str = "src/assets/images/door.png";
+pub const DEFAULT_SCREAMER_IMAGE: &str = "src/assets/images/screamer.png";
+pub const DEFAULT_SCREAMER_SOUND: &str = "src/assets/music/screamer.mp3";
+pub const DEFAULT_HINTS: u32 = 3;
+pub const DEFAULT_HINT_PENALTY: f32 = 10.0;
+
+pub struct Fog {
+    pub distance: f32, // Distancia a la que las paredes desaparecen por completo
+    pub color: u32,
+}
+
+pub struct ScreamerSettings {
+    pub enabled: bool,
+    pub interval: f32, // Segundos entre apariciones
+    pub image: String,
+    pub sound: String,
+}
+
+// Todo lo que describe un nivel además de sus paredes
+pub struct LevelMeta {
+    pub title: Option<String>,
+    pub author: Option<String>,
+    pub music: Option<String>,
+    pub sky_color: u32,
+    pub floor_color: u32,
+    pub wall_texture: String,
+    pub goal_texture: String,
+    pub fog: Option<Fog>,
+    pub time_limit: Option<f32>, // Segundos
+    pub hints: u32,
+    pub hint_penalty: f32, // Segundos que se suman al tiempo por cada pista
+    pub screamer: ScreamerSettings,
+}
+
+impl Default for LevelMeta {
+    fn default() -> Self {
+        LevelMeta {
+            title: None,
+            author: None,
+            music: None,
+            sky_color: DEFAULT_SKY_COLOR,
+            floor_color: DEFAULT_FLOOR_COLOR,
+            wall_texture: DEFAULT_WALL_TEXTURE.to_string(),
+            goal_texture: DEFAULT_GOAL_TEXTURE.to_string(),
+            fog: None,
+            time_limit: None,
+            hints: DEFAULT_HINTS,
+            hint_penalty: DEFAULT_HINT_PENALTY,
+            screamer: ScreamerSettings {
+                enabled: true,
+                interval: 11.0,
+                image: DEFAULT_SCREAMER_IMAGE.to_string(),
+                sound: DEFAULT_SCREAMER_SOUND.to_string(),
+            },
+        }
+    }
+}
+
+impl LevelMeta {
+    // Lee líneas "clave: valor"; las vacías y las que empiezan con '#' se ignoran
+    pub fn parse(lines: &[&str]) -> Result<LevelMeta, MazeError> {
+        let mut meta = LevelMeta::default();
+        let mut fog_distance = None;
+        let mut fog_color = 0x000000;
+
+        for (index, raw) in lines.iter().enumerate() {
+            let line = index + 1;
+            let raw = raw.trim();
+            if raw.is_empty() || raw.starts_with('#') {
+                continue;
+            }
+
+            let Some((key, value)) = raw.split_once(':') else {
+                return Err(MazeError::InvalidHeader { line, message: format!("se esperaba \"clave: valor\", se encontró {:?}", raw) });
+            };
+            let (key, value) = (key.trim(), value.trim());
+            let invalid = |what: &str| MazeError::InvalidHeader { line, message: format!("{} inválido para {}: {:?}", what, key, value) };
+
+            match key {
+                "title" => meta.title = Some(value.to_string()),
+                "author" => meta.author = Some(value.to_string()),
+                "music" => meta.music = Some(value.to_string()),
+                "sky" => meta.sky_color = parse_color(value).ok_or_else(|| invalid("color"))?,
+                "floor" => meta.floor_color = parse_color(value).ok_or_else(|| invalid("color"))?,
+                "wall_texture" => meta.wall_texture = value.to_string(),
+                "goal_texture" => meta.goal_texture = value.to_string(),
+                "fog" => fog_distance = Some(parse_positive(value).ok_or_else(|| invalid("número"))?),
+                "fog_color" => fog_color = parse_color(value).ok_or_else(|| invalid("color"))?,
+                "time_limit" => meta.time_limit = Some(parse_positive(value).ok_or_else(|| invalid("número"))?),
+                "hints" => meta.hints = value.parse().map_err(|_| invalid("número"))?,
+                "hint_penalty" => meta.hint_penalty = parse_non_negative(value).ok_or_else(|| invalid("número"))?,
+                "screamer" => meta.screamer.enabled = parse_bool(value).ok_or_else(|| invalid("valor"))?,
+                "screamer_interval" => meta.screamer.interval = parse_positive(value).ok_or_else(|| invalid("número"))?,
+                "screamer_image" => meta.screamer.image = value.to_string(),
+                "screamer_sound" => meta.screamer.sound = value.to_string(),
+                _ => return Err(MazeError::InvalidHeader { line, message: format!("clave desconocida {:?}", key) }),
+            }
+        }
+
+        meta.fog = fog_distance.map(|distance| Fog { distance, color: fog_color });
+        Ok(meta)
+    }
+}
+
+// Acepta "#87CEEB" o "0x87CEEB"
+fn parse_color(value: &str) -> Option<u32> {
+    let hex = value.strip_prefix('#').or_else(|| value.strip_prefix("0x"))?;
+    if hex.len() != 6 {
+        return None;
+    }
+    u32::from_str_radix(hex, 16).ok()
+}
+
+fn parse_positive(value: &str) -> Option<f32> {
+    value.parse::<f32>().ok().filter(|number| number.is_finite() && *number > 0.0)
+}
+
+fn parse_non_negative(value: &str) -> Option<f32> {
+    value.parse::<f32>().ok().filter(|number| number.is_finite() && *number >= 0.0)
+}
+
+fn parse_bool(value: &str) -> Option<bool> {
+    match value {
+        "true" | "on" | "yes" => Some(true),
+        "false" | "off" | "no" => Some(false),
+        _ => None,
+    }
+}
+
+pub struct Maze {
+    cells: Vec<Vec<char>>,
+    pub width: usize,
+    pub height: usize,
+    pub block_size: usize,
+    pub spawn: (usize, usize), // (columna, fila)
+    pub spawn_angle: f32,
+    pub goal: (usize, usize),  // (columna, fila)
+    pub meta: LevelMeta,
+}
+
+#[derive(Debug)]
+pub enum MazeError {
+    Io { path: String, source: std::io::Error },
+    Texture { key: &'static str, path: String, source: image::ImageError },
+    InvalidHeader { line: usize, message: String },
+    Empty,
+    RaggedRow { line: usize, expected: usize, found: usize },
+    UnknownCell { line: usize, column: usize, found: char },
+    UnenclosedBorder { line: usize, column: usize },
+    MissingGoal,
+    DuplicateGoal { line: usize, column: usize },
+    MissingSpawn,
+    DuplicateSpawn { line: usize, column: usize },
+    SpawnInWall { line: usize, column: usize },
 }
 
-pub fn is_wall(maze: &Vec<Vec<char>>, x: usize, y: usize) -> bool {
-    let i = x / 100;
-    let j = y / 100;
-    if j >= maze.len() || i >= maze[j].len() {
-        return true;
+impl fmt::Display for MazeError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            MazeError::Io { path, source } => write!(f, "No se pudo abrir el archivo de laberinto {}: {}", path, source),
+            MazeError::Texture { key, path, source } => write!(f, "No se pudo abrir la textura {} de {}: {}", path, key, source),
+            MazeError::InvalidHeader { line, message } => write!(f, "Línea {}: encabezado inválido, {}", line, message),
+            MazeError::Empty => write!(f, "El laberinto está vacío"),
+            MazeError::RaggedRow { line, expected, found } => {
+                write!(f, "Línea {}: la fila tiene {} columnas, se esperaban {}", line, found, expected)
+            }
+            MazeError::UnknownCell { line, column, found } => {
+                write!(f, "Línea {}, columna {}: carácter desconocido {:?}", line, column, found)
+            }
+            MazeError::UnenclosedBorder { line, column } => {
+                write!(f, "Línea {}, columna {}: el borde del laberinto está abierto", line, column)
+            }
+            MazeError::MissingGoal => write!(f, "El laberinto no tiene meta ('{}')", GOAL),
+            MazeError::DuplicateGoal { line, column } => {
+                write!(f, "Línea {}, columna {}: el laberinto ya tiene una meta", line, column)
+            }
+            MazeError::MissingSpawn => {
+                write!(f, "El laberinto no tiene punto de aparición ('{}' o una flecha ^ > v <)", SPAWN)
+            }
+            MazeError::DuplicateSpawn { line, column } => {
+                write!(f, "Línea {}, columna {}: el laberinto ya tiene un punto de aparición", line, column)
+            }
+            MazeError::SpawnInWall { line, column } => {
+                write!(f, "Línea {}, columna {}: el punto de aparición está dentro de una pared", line, column)
+            }
+        }
+    }
+}
+
+impl std::error::Error for MazeError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            MazeError::Io { source, .. } => Some(source),
+            MazeError::Texture { source, .. } => Some(source),
+            _ => None,
+        }
+    }
+}
+
+impl Maze {
+    pub fn parse(text: &str) -> Result<Maze, MazeError> {
+        let lines: Vec<&str> = text.lines().collect();
+
+        // El encabezado de metadatos es opcional
+        let (meta, first_line) = match lines.iter().position(|line| line.trim() == HEADER_END) {
+            Some(end) => (LevelMeta::parse(&lines[..end])?, end + 1),
+            None => (LevelMeta::default(), 0),
+        };
+        let lines = &lines[first_line..];
+        let line_number = |row: usize| first_line + row + 1;
+
+        // Las líneas vacías al final del archivo no forman parte del laberinto
+        let last = lines.iter().rposition(|line| !line.trim().is_empty()).ok_or(MazeError::Empty)?;
+
+        let mut cells: Vec<Vec<char>> = Vec::new();
+        let mut goal = None;
+        let mut spawn = None;
+
+        for (row, line) in lines[..=last].iter().enumerate() {
+            let mut chars: Vec<char> = line.chars().collect();
+
+            if let Some(first) = cells.first() {
+                if chars.len() != first.len() {
+                    return Err(MazeError::RaggedRow { line: line_number(row), expected: first.len(), found: chars.len() });
+                }
+            }
+
+            for (col, cell) in chars.iter_mut().enumerate() {
+                // El marcador de aparición es una celda libre más
+                if let Some(facing) = spawn_facing(*cell) {
+                    if spawn.is_some() {
+                        return Err(MazeError::DuplicateSpawn { line: line_number(row), column: col + 1 });
+                    }
+                    spawn = Some(((col, row), facing));
+                    *cell = ' ';
+                    continue;
+                }
+
+                let cell = *cell;
+                if cell != ' ' && cell != GOAL && !WALLS.contains(&cell) {
+                    return Err(MazeError::UnknownCell { line: line_number(row), column: col + 1, found: cell });
+                }
+                if cell == GOAL {
+                    if goal.is_some() {
+                        return Err(MazeError::DuplicateGoal { line: line_number(row), column: col + 1 });
+                    }
+                    goal = Some((col, row));
+                }
+            }
+
+            cells.push(chars);
+        }
+
+        let height = cells.len();
+        let width = cells[0].len();
+        let on_border = |col: usize, row: usize| row == 0 || col == 0 || row == height - 1 || col == width - 1;
+
+        // En el borde el punto de aparición quedaría dentro de la pared exterior
+        if let Some(((col, row), _)) = spawn {
+            if on_border(col, row) {
+                return Err(MazeError::SpawnInWall { line: line_number(row), column: col + 1 });
+            }
+        }
+
+        for (row, line) in cells.iter().enumerate() {
+            for (col, &cell) in line.iter().enumerate() {
+                if on_border(col, row) && cell == ' ' {
+                    return Err(MazeError::UnenclosedBorder { line: line_number(row), column: col + 1 });
+                }
+            }
+        }
+
+        let goal = goal.ok_or(MazeError::MissingGoal)?;
+        let ((spawn_col, spawn_row), spawn_angle) = spawn.ok_or(MazeError::MissingSpawn)?;
+
+        let maze = Maze {
+            cells,
+            width,
+            height,
+            block_size: DEFAULT_BLOCK_SIZE,
+            spawn: (spawn_col, spawn_row),
+            spawn_angle,
+            goal,
+            meta,
+        };
+
+        // Encerrado entre paredes el jugador no podría moverse
+        let boxed_in = maze.is_wall_cell(spawn_col - 1, spawn_row)
+            && maze.is_wall_cell(spawn_col + 1, spawn_row)
+            && maze.is_wall_cell(spawn_col, spawn_row - 1)
+            && maze.is_wall_cell(spawn_col, spawn_row + 1);
+        if boxed_in {
+            return Err(MazeError::SpawnInWall { line: line_number(spawn_row), column: spawn_col + 1 });
+        }
+
+        Ok(maze)
+    }
+
+    pub fn cell(&self, col: usize, row: usize) -> Option<char> {
+        self.cells.get(row).and_then(|line| line.get(col)).copied()
+    }
+
+    pub fn rows(&self) -> impl Iterator<Item = &[char]> {
+        self.cells.iter().map(|line| line.as_slice())
+    }
+
+    // Fuera del laberinto todo cuenta como pared
+    pub fn is_wall_cell(&self, col: usize, row: usize) -> bool {
+        self.cell(col, row) != Some(' ')
+    }
+
+    pub fn is_wall(&self, x: usize, y: usize) -> bool {
+        self.is_wall_cell(x / self.block_size, y / self.block_size)
+    }
+
+    // Un círculo choca si se superpone con cualquier celda de pared que toque
+    pub fn circle_hits_wall(&self, center: Vec2, radius: f32) -> bool {
+        let block = self.block_size as f32;
+        let first_col = ((center.x - radius) / block).floor();
+        let last_col = ((center.x + radius) / block).floor();
+        let first_row = ((center.y - radius) / block).floor();
+        let last_row = ((center.y + radius) / block).floor();
+        if first_col < 0.0 || first_row < 0.0 {
+            return true;
+        }
+
+        for row in first_row as usize..=last_row as usize {
+            for col in first_col as usize..=last_col as usize {
+                if !self.is_wall_cell(col, row) {
+                    continue;
+                }
+                // Punto de la celda más cercano al centro del círculo
+                let closest_x = center.x.clamp(col as f32 * block, (col + 1) as f32 * block);
+                let closest_y = center.y.clamp(row as f32 * block, (row + 1) as f32 * block);
+                if (center.x - closest_x).powi(2) + (center.y - closest_y).powi(2) < radius * radius {
+                    return true;
+                }
+            }
+        }
+        false
+    }
+
+    // Mueve un círculo resolviendo X e Y por separado: si un eje choca se avanza
+    // lo más posible en ese eje y el otro sigue libre, así se desliza por las paredes.
+    pub fn slide_circle(&self, from: Vec2, delta: Vec2, radius: f32) -> Vec2 {
+        let mut pos = from;
+        for axis in 0..2 {
+            let mut step = delta[axis];
+            let mut target = pos;
+            target[axis] += step;
+            if !self.circle_hits_wall(target, radius) {
+                pos = target;
+                continue;
+            }
+
+            // Búsqueda binaria del tramo más largo que no choca
+            let mut free = 0.0;
+            for _ in 0..8 {
+                step /= 2.0;
+                let mut target = pos;
+                target[axis] += free + step;
+                if !self.circle_hits_wall(target, radius) {
+                    free += step;
+                }
+            }
+            pos[axis] += free;
+        }
+        pos
+    }
+
+    // Se gana al llegar a la meta o a cualquier celda vecina (diagonales incluidas)
+    pub fn is_near_goal(&self, col: usize, row: usize) -> bool {
+        let (goal_col, goal_row) = self.goal;
+        col.abs_diff(goal_col) <= 1 && row.abs_diff(goal_row) <= 1
+    }
+
+    // Posición en el mundo del centro de una celda
+    pub fn cell_center(&self, col: usize, row: usize) -> Vec2 {
+        let half = self.block_size as f32 / 2.0;
+        Vec2::new((col * self.block_size) as f32 + half, (row * self.block_size) as f32 + half)
+    }
+}
+
+pub fn load_maze(filename: &str) -> Result<Maze, MazeError> {
+    let text = fs::read_to_string(filename).map_err(|source| MazeError::Io { path: filename.to_string(), source })?;
+    let maze = Maze::parse(&text)?;
+
+    // Las texturas se cargan con el nivel; un error de tipeo no debe aparecer recién al dibujar
+    for (key, path) in [("wall_texture", &maze.meta.wall_texture), ("goal_texture", &maze.meta.goal_texture)] {
+        Texture::load(path).map_err(|source| MazeError::Texture { key, path: path.clone(), source })?;
     }
-    maze[j][i] != ' '
+    Ok(maze)
 }
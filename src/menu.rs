@@ -0,0 +1,320 @@
+use std::time::Duration;
+
+use minifb::Key;
+
+use crate::backend::Backend;
+use crate::daily::{times_for, DailyRecord};
+use crate::framebuffer::Framebuffer;
+use crate::input::{key_name, Action, Bindings};
+use crate::levels::Level;
+use crate::render::render_thumbnail;
+use crate::text::{draw_string, TextStyle};
+use crate::settings::{Settings, FOV_RANGE, MINIMAP_RANGE, RESOLUTIONS, SENSITIVITY_RANGE, SMOOTHING_RANGE, VOLUME_RANGE};
+use crate::ui::{Rect, Ui, FOCUS_COLOR, ROW_HEIGHT, TEXT_COLOR};
+
+pub const LEVELS_PER_PAGE: usize = 8; // Con pantallas bajas entran menos
+
+const PANEL_X: usize = 60;
+const PANEL_Y: usize = 120;
+const PANEL_WIDTH: usize = 520;
+const PREVIEW_WIDTH: usize = 600; // Tamaño máximo de la vista previa
+const PREVIEW_HEIGHT: usize = 420;
+const PREVIEW_FOOTER: usize = 150; // Autor y mejores tiempos debajo de la vista previa
+const SMALL_SIZE: f32 = 16.0;
+const ERROR_COLOR: u32 = 0xFF4040;
+const WAITING_COLOR: u32 = 0xFFD700;
+
+const DIGIT_KEYS: [Key; 9] = [
+    Key::Key1, Key::Key2, Key::Key3, Key::Key4, Key::Key5, Key::Key6, Key::Key7, Key::Key8, Key::Key9,
+];
+
+// Lista de niveles con navegación por flechas, páginas, mouse y vista previa del laberinto
+pub struct LevelMenu {
+    pub levels: Vec<Level>,
+    pub selected: usize,
+    thumbnail: Option<(usize, Rect, Framebuffer)>, // Nivel, lugar donde se dibuja y vista previa
+    daily_times: Vec<DailyRecord>,
+    ui: Ui,
+}
+
+impl LevelMenu {
+    pub fn new(levels: Vec<Level>) -> Self {
+        LevelMenu { levels, selected: 0, thumbnail: None, daily_times: Vec::new(), ui: Ui::default() }
+    }
+
+    // Cambia el laberinto aleatorio por otro con una semilla nueva
+    pub fn reroll(&mut self, index: usize) {
+        self.levels[index] = Level::random();
+        if self.thumbnail.as_ref().is_some_and(|(cached, _, _)| *cached == index) {
+            self.thumbnail = None;
+        }
+    }
+
+    // Tiempo desde la última tecla o movimiento del mouse, para el modo demostración
+    pub fn idle(&self) -> Duration {
+        self.ui.idle()
+    }
+
+    // Al volver al menú la tecla que lo abrió no debe elegir nada
+    pub fn reset_keys(&mut self) {
+        self.ui.reset();
+    }
+
+    // Tecla presionada en el último cuadro
+    pub fn pressed(&self, key: Key) -> bool {
+        self.ui.pressed(key)
+    }
+
+    // Solo lee la entrada, sin dibujar ni elegir: mientras se ve la demostración
+    pub fn watch(&mut self, window: &dyn Backend) {
+        self.ui.begin(window);
+        self.ui.take_pressed();
+        self.ui.end();
+    }
+
+    // Dibuja el menú y devuelve el índice del nivel elegido, si el jugador eligió uno válido
+    pub fn update(&mut self, window: &dyn Backend, framebuffer: &mut Framebuffer) -> Option<usize> {
+        let (list, preview, rows) = level_layout(framebuffer.width, framebuffer.height);
+        self.ui.begin(window);
+        self.ui.panel(framebuffer, Rect::new(list.x - 20, list.y - 20, list.width + 40, rows * ROW_HEIGHT + 80));
+
+        // Los niveles con errores se muestran pero no se pueden elegir
+        let items: Vec<(String, u32)> = self.levels.iter().enumerate()
+            .map(|(index, level)| {
+                let color = if level.maze.is_ok() { TEXT_COLOR } else { ERROR_COLOR };
+                (format!("{}. {}", index % rows + 1, level.title), color)
+            })
+            .collect();
+        let area = Rect::new(list.x - 10, list.y - 8, list.width + 20, (rows + 1) * ROW_HEIGHT);
+        let mut chosen = self.ui.list(framebuffer, area, &items, &mut self.selected, rows);
+
+        // Los números eligen directamente un nivel de la página actual
+        let first = self.selected / rows * rows;
+        for (offset, key) in DIGIT_KEYS.iter().take(rows).enumerate() {
+            if self.ui.pressed(*key) && first + offset < self.levels.len() {
+                self.selected = first + offset;
+                chosen = Some(self.selected);
+            }
+        }
+        self.ui.end();
+
+        self.draw_preview(framebuffer, preview);
+        chosen.filter(|&index| self.levels[index].maze.is_ok())
+    }
+
+    fn draw_preview(&mut self, framebuffer: &mut Framebuffer, area: Rect) {
+        self.ui.panel(framebuffer, Rect::new(area.x - 10, area.y - 10, area.width + 20, area.height + 20));
+
+        let Some(level) = self.levels.get(self.selected) else {
+            return;
+        };
+
+        let maze = match &level.maze {
+            Ok(maze) => maze,
+            Err(err) => {
+                let style = TextStyle { size: SMALL_SIZE, color: ERROR_COLOR, max_width: Some(area.width), ..TextStyle::default() };
+                draw_string(framebuffer, &err.to_string(), area.x as i32, area.y as i32, &style);
+                return;
+            }
+        };
+
+        // La vista previa solo se vuelve a dibujar cuando cambia la selección o la resolución
+        if self.thumbnail.as_ref().map(|(index, cached, _)| (*index, *cached)) != Some((self.selected, area)) {
+            self.thumbnail = Some((self.selected, area, render_thumbnail(maze, area.width, area.height)));
+            self.daily_times = level.daily.map(times_for).unwrap_or_default();
+        }
+        let (_, _, thumbnail) = self.thumbnail.as_ref().unwrap();
+
+        let xo = area.x + area.width.saturating_sub(thumbnail.width) / 2;
+        let yo = area.y + area.height.saturating_sub(thumbnail.height) / 2;
+        framebuffer.copy_from(thumbnail, xo as i32, yo as i32);
+
+        if let Some(author) = &maze.meta.author {
+            self.ui.label(framebuffer, author, area.x, area.y + area.height + 12, TEXT_COLOR);
+        }
+
+        // Mejores tiempos del reto diario de hoy
+        for (i, record) in self.daily_times.iter().take(5).enumerate() {
+            let text = format!("{}. {:.2} {}", i + 1, record.seconds, record.player);
+            let style = TextStyle { size: SMALL_SIZE, ..TextStyle::default() };
+            draw_string(framebuffer, &text, area.x as i32, (area.y + area.height + 50 + i * 20) as i32, &style);
+        }
+    }
+}
+
+// Pantalla para cambiar los controles: Enter o un clic espera una tecla nueva
+// para la acción elegida, Retroceso le quita todas y Escape cierra.
+pub struct ControlsMenu {
+    pub bindings: Bindings,
+    waiting: Option<Action>,
+    ui: Ui,
+}
+
+impl ControlsMenu {
+    pub fn new(bindings: Bindings) -> Self {
+        ControlsMenu { bindings, waiting: None, ui: Ui::default() }
+    }
+
+    // Dibuja la pantalla; devuelve false cuando el jugador la cierra
+    pub fn update(&mut self, window: &dyn Backend, framebuffer: &mut Framebuffer) -> bool {
+        self.ui.begin(window);
+
+        // Mientras espera, la primera tecla es la nueva y ningún control la ve
+        if let Some(action) = self.waiting {
+            if let Some(&key) = self.ui.take_pressed().first() {
+                if key != Key::Escape {
+                    self.bindings.bind(action, key);
+                }
+                self.waiting = None;
+            }
+        }
+        let open = self.waiting.is_some() || !self.ui.pressed(Key::Escape);
+
+        self.ui.accent = if self.waiting.is_some() { WAITING_COLOR } else { FOCUS_COLOR };
+        self.ui.panel(framebuffer, panel(PANEL_X, PANEL_Y, Action::ALL.len()));
+        for (index, action) in Action::ALL.iter().enumerate() {
+            let keys: Vec<String> = self.bindings.keys(*action).iter().map(|key| key_name(*key)).collect();
+            if self.ui.field(framebuffer, row(PANEL_X, PANEL_Y, index), action.label(), &keys.join(", ")) {
+                self.waiting = Some(*action);
+            }
+            if self.ui.focus() == index && self.waiting.is_none() && self.ui.pressed(Key::Backspace) {
+                self.bindings.clear(*action);
+            }
+        }
+        self.ui.end();
+        open
+    }
+}
+
+const SETTINGS_ROWS: usize = 13;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum SettingsEvent {
+    Controls,
+    Close,
+}
+
+// Pantalla de configuración: arriba y abajo eligen la opción, izquierda y
+// derecha cambian su valor; con el mouse se arrastran los deslizadores.
+pub struct SettingsMenu {
+    pub settings: Settings,
+    ui: Ui,
+}
+
+impl SettingsMenu {
+    pub fn new(settings: Settings) -> Self {
+        SettingsMenu { settings, ui: Ui::default() }
+    }
+
+    // Al volver de la pantalla de controles, el Escape que la cerró no debe cerrar esta
+    pub fn reset_keys(&mut self) {
+        self.ui.reset();
+    }
+
+    // Dibuja la pantalla y aplica los cambios a `settings`
+    pub fn update(&mut self, window: &dyn Backend, framebuffer: &mut Framebuffer) -> Option<SettingsEvent> {
+        let ui = &mut self.ui;
+        let settings = &mut self.settings;
+        ui.begin(window);
+        ui.panel(framebuffer, panel(PANEL_X, PANEL_Y, SETTINGS_ROWS));
+
+        let row = |index| row(PANEL_X, PANEL_Y, index);
+        let percent = |value: f32| format!("{}%", (value * 100.0).round());
+
+        // La resolución actual puede no estar en la lista si vino del archivo
+        let mut resolutions = RESOLUTIONS.to_vec();
+        if !resolutions.contains(&(settings.width, settings.height)) {
+            resolutions.push((settings.width, settings.height));
+            resolutions.sort();
+        }
+        let mut index = resolutions.iter().position(|&size| size == (settings.width, settings.height)).unwrap() as f32;
+        let last = (resolutions.len() - 1) as f32;
+        let resolution_text = |index: f32| {
+            let (width, height) = resolutions[index as usize];
+            format!("{}x{}", width, height)
+        };
+        if ui.slider(framebuffer, row(0), "Resolución", &mut index, (0.0, last), 1.0, resolution_text) {
+            (settings.width, settings.height) = resolutions[index as usize];
+        }
+
+        ui.slider(framebuffer, row(1), "Campo de visión", &mut settings.fov, FOV_RANGE, 5.0, |value| format!("{}", value.round()));
+        ui.slider(framebuffer, row(2), "Sensibilidad del mouse", &mut settings.mouse_sensitivity, SENSITIVITY_RANGE, 0.001, |value| format!("{:.3}", value));
+        ui.toggle(framebuffer, row(3), "Invertir mouse", &mut settings.invert_mouse);
+        ui.slider(framebuffer, row(4), "Suavizado del mouse", &mut settings.mouse_smoothing, SMOOTHING_RANGE, 0.1, percent);
+        ui.slider(framebuffer, row(5), "Volumen de la música", &mut settings.music_volume, VOLUME_RANGE, 0.1, percent);
+        ui.slider(framebuffer, row(6), "Volumen de los efectos", &mut settings.effects_volume, VOLUME_RANGE, 0.1, percent);
+        ui.slider(framebuffer, row(7), "Tamaño del minimapa", &mut settings.minimap_scale, MINIMAP_RANGE, 0.05, percent);
+        ui.toggle(framebuffer, row(8), "Texturas", &mut settings.textures);
+        ui.toggle(framebuffer, row(9), "Niebla", &mut settings.fog);
+        ui.toggle(framebuffer, row(10), "Mostrar FPS", &mut settings.show_fps);
+        let controls = ui.button(framebuffer, row(11), "Controles");
+        let close = ui.button(framebuffer, row(12), "Volver") || ui.pressed(Key::Escape);
+        ui.end();
+
+        if close {
+            Some(SettingsEvent::Close)
+        } else if controls {
+            Some(SettingsEvent::Controls)
+        } else {
+            None
+        }
+    }
+}
+
+// Lista vertical de opciones, como las del menú principal
+pub struct OptionsMenu {
+    pub options: Vec<&'static str>,
+    ui: Ui,
+}
+
+impl OptionsMenu {
+    pub fn new(options: &[&'static str]) -> Self {
+        OptionsMenu { options: options.to_vec(), ui: Ui::default() }
+    }
+
+    // Dibuja las opciones con el texto de la primera en (x, y) y devuelve la elegida
+    pub fn update(&mut self, window: &dyn Backend, framebuffer: &mut Framebuffer, x: usize, y: usize) -> Option<usize> {
+        self.ui.begin(window);
+        self.ui.panel(framebuffer, panel(x, y, self.options.len()));
+        let mut chosen = None;
+        for (index, option) in self.options.iter().enumerate() {
+            if self.ui.button(framebuffer, row(x, y, index), option) {
+                chosen = Some(index);
+            }
+        }
+        self.ui.end();
+        chosen
+    }
+
+    // Tecla presionada en el último cuadro, para atajos propios de cada pantalla
+    pub fn pressed(&self, key: Key) -> bool {
+        self.ui.pressed(key)
+    }
+}
+
+// Texto de la lista, vista previa y filas por página del menú de niveles para una
+// pantalla de width x height. En 1300x900 o más se usan los tamaños máximos; en
+// pantallas más chicas la lista y la vista previa se reparten el espacio.
+fn level_layout(width: usize, height: usize) -> (Rect, Rect, usize) {
+    let margin = PANEL_X.min(width / 16).max(20);
+    let top = PANEL_Y.min(height / 6).max(20);
+
+    let list_width = PANEL_WIDTH.min(width.saturating_sub(3 * margin) * 9 / 20);
+    let rows = (height.saturating_sub(top + 80) / ROW_HEIGHT).clamp(1, LEVELS_PER_PAGE);
+    let list = Rect::new(margin, top, list_width, rows * ROW_HEIGHT);
+
+    let preview_x = margin + list_width + margin;
+    let preview_width = PREVIEW_WIDTH.min(width.saturating_sub(preview_x + margin)).max(ROW_HEIGHT);
+    let preview_height = PREVIEW_HEIGHT.min(height.saturating_sub(top + PREVIEW_FOOTER + margin)).max(ROW_HEIGHT);
+    (list, Rect::new(preview_x, top, preview_width, preview_height), rows)
+}
+
+// Fondo de un panel de `rows` filas cuyo texto empieza en (x, y)
+fn panel(x: usize, y: usize, rows: usize) -> Rect {
+    Rect::new(x - 20, y - 20, PANEL_WIDTH + 40, rows * ROW_HEIGHT + 40)
+}
+
+// Franja de la fila `index`, la que se resalta con el foco
+fn row(x: usize, y: usize, index: usize) -> Rect {
+    Rect::new(x - 10, y + index * ROW_HEIGHT - 8, PANEL_WIDTH + 20, ROW_HEIGHT - 8)
+}
@@ -1,58 +1,112 @@
 use nalgebra_glm::Vec2;
-use minifb::{Key, Window};
-use crate::maze;
+use std::f32::consts::PI;
+use crate::backend::Backend;
+use crate::input::{Action, Bindings, MouseLook};
+use crate::maze::Maze;
 
+#[derive(Clone)]
 pub struct Player {
     pub pos: Vec2,
     pub a: f32, // Ángulo de visión
     pub fov: f32, // Campo de visión
+    pub radius: f32, // Tamaño del jugador para los choques con las paredes
 }
 
-static mut LAST_MOUSE_X: f32 = 0.0;
+impl Player {
+    // Coloca al jugador en el punto de aparición declarado en el laberinto
+    pub fn at_spawn(maze: &Maze) -> Player {
+        let (col, row) = maze.spawn;
+        Player {
+            pos: maze.cell_center(col, row),
+            a: maze.spawn_angle,
+            fov: PI / 4.0,
+            radius: DEFAULT_RADIUS,
+        }
+    }
+
+    // Estado intermedio entre dos pasos de la simulación, para dibujar sin saltos
+    pub fn interpolate(&self, next: &Player, alpha: f32) -> Player {
+        Player {
+            pos: self.pos + (next.pos - self.pos) * alpha,
+            a: self.a + (next.a - self.a) * alpha,
+            fov: next.fov,
+            radius: next.radius,
+        }
+    }
+}
 
-pub fn process_events(window: &Window, player: &mut Player, maze: &Vec<Vec<char>>) {
-    const MOVE_SPEED: f32 = 10.0;
-    const ROTATION_SPEED: f32 = 0.005;
-    const STRAFE_SPEED: f32 = 10.0;
+// La simulación avanza en pasos fijos, sin importar los FPS
+pub const TICK: f32 = 1.0 / 60.0;
 
-    let (mouse_x, _) = window.get_mouse_pos(minifb::MouseMode::Pass).unwrap_or((0.0, 0.0));
+pub const MOVE_SPEED: f32 = 300.0; // Unidades por segundo
+pub const STRAFE_SPEED: f32 = 300.0; // Unidades por segundo
+pub const KEY_ROTATION_SPEED: f32 = 2.4; // Radianes por segundo
 
-    unsafe {
-        let mouse_dx = mouse_x - LAST_MOUSE_X;
-        LAST_MOUSE_X = mouse_x;
+// Debe ser menor que media celda para caber en los pasillos
+pub const DEFAULT_RADIUS: f32 = 20.0;
 
-        player.a += mouse_dx * ROTATION_SPEED;
+// Entrada de un cuadro, independiente de la ventana: así se puede grabar y reproducir
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct InputFrame {
+    pub actions: u16, // Un bit por acción de Action::ALL
+    pub look: f32, // Radianes de giro con el mouse
+    pub dt: f32, // Segundos desde el cuadro anterior
+}
+
+impl InputFrame {
+    pub fn capture(window: &dyn Backend, bindings: &Bindings, mouse: &mut MouseLook, dt: f32) -> InputFrame {
+        let mut actions = 0;
+        for action in Action::ALL {
+            if bindings.is_down(window, action) {
+                actions |= 1 << action.index();
+            }
+        }
+
+        InputFrame { actions, look: mouse.turn(window, dt), dt }
     }
 
-    let forward_x = player.pos.x + MOVE_SPEED * player.a.cos();
-    let forward_y = player.pos.y + MOVE_SPEED * player.a.sin();
+    pub fn is_down(&self, action: Action) -> bool {
+        self.actions & (1 << action.index()) != 0
+    }
 
-    let backward_x = player.pos.x - MOVE_SPEED * player.a.cos();
-    let backward_y = player.pos.y - MOVE_SPEED * player.a.sin();
+    pub fn press(&mut self, action: Action) {
+        self.actions |= 1 << action.index();
+    }
+}
 
-    let right_vector = nalgebra_glm::rotate_vec2(&Vec2::new(player.a.cos(), player.a.sin()), -std::f32::consts::PI / 2.0);
-    let strafe_right_x = player.pos.x - STRAFE_SPEED * right_vector.x;
-    let strafe_right_y = player.pos.y - STRAFE_SPEED * right_vector.y;
+pub fn process_events(window: &dyn Backend, bindings: &Bindings, mouse: &mut MouseLook, player: &mut Player, maze: &Maze, dt: f32) {
+    apply_input(player, maze, &InputFrame::capture(window, bindings, mouse, dt));
+}
 
-    let strafe_left_x = player.pos.x + STRAFE_SPEED * right_vector.x;
-    let strafe_left_y = player.pos.y + STRAFE_SPEED * right_vector.y;
+// Mueve al jugador según un cuadro de entrada que dura `input.dt` segundos; no lee la ventana ni el reloj
+pub fn apply_input(player: &mut Player, maze: &Maze, input: &InputFrame) {
+    player.a += input.look;
 
-    if window.is_key_down(Key::W) && !maze::is_wall(maze, forward_x as usize, forward_y as usize) {
-        player.pos.x = forward_x;
-        player.pos.y = forward_y;
+    // Girar con las flechas, para cuando no hay mouse (por ejemplo en la terminal)
+    if input.is_down(Action::TurnLeft) {
+        player.a -= KEY_ROTATION_SPEED * input.dt;
     }
-    if window.is_key_down(Key::S) && !maze::is_wall(maze, backward_x as usize, backward_y as usize) {
-        player.pos.x = backward_x;
-        player.pos.y = backward_y;
+    if input.is_down(Action::TurnRight) {
+        player.a += KEY_ROTATION_SPEED * input.dt;
     }
 
-    if window.is_key_down(Key::D) && !maze::is_wall(maze, strafe_right_x as usize, strafe_right_y as usize) {
-        player.pos.x = strafe_right_x;
-        player.pos.y = strafe_right_y;
+    let forward = Vec2::new(player.a.cos(), player.a.sin());
+    let right = Vec2::new(-player.a.sin(), player.a.cos());
+
+    // Todas las teclas de movimiento se suman en un solo desplazamiento
+    let mut delta = Vec2::zeros();
+    if input.is_down(Action::MoveForward) {
+        delta += forward * MOVE_SPEED;
     }
-    if window.is_key_down(Key::A) && !maze::is_wall(maze, strafe_left_x as usize, strafe_left_y as usize) {
-        player.pos.x = strafe_left_x;
-        player.pos.y = strafe_left_y;
+    if input.is_down(Action::MoveBackward) {
+        delta -= forward * MOVE_SPEED;
+    }
+    if input.is_down(Action::StrafeRight) {
+        delta += right * STRAFE_SPEED;
+    }
+    if input.is_down(Action::StrafeLeft) {
+        delta -= right * STRAFE_SPEED;
     }
-}
 
+    player.pos = maze.slide_circle(player.pos, delta * input.dt, player.radius);
+}
@@ -1,6 +1,7 @@
 
 use crate::framebuffer::Framebuffer;
 use crate::player::Player; 
+use crate::maze::Maze;
 
 pub struct Intersect {
     pub distance: f32,
@@ -10,7 +11,7 @@ pub struct Intersect {
 
 pub fn cast_ray(
     framebuffer: &mut Framebuffer,
-    maze: &Vec<Vec<char>>,
+    maze: &Maze,
     player: &Player,
     a: f32, 
     block_size: usize,
@@ -45,10 +46,10 @@ pub fn cast_ray(
             framebuffer.point(x,y);
         }
 
-        if maze[j][i] != ' ' {
+        if maze.is_wall_cell(i, j) {
             return Intersect {
                 distance: d, 
-                impact: maze[j][i],
+                impact: maze.cell(i, j).unwrap_or('+'),
                 tx: maxhit * 128 / block_size, 
             };
         }
@@ -56,34 +57,28 @@ pub fn cast_ray(
     }
 }
 
-pub fn cast_ray_minimap(framebuffer: &mut Framebuffer, maze: &Vec<Vec<char>>, player: &Player, angle: f32, block_size: usize, minimap_x: usize, minimap_y: usize, scale: f32) {
+// El rayo avanza en coordenadas del mundo; `scale` solo se usa para dibujarlo en el minimapa
+pub fn cast_ray_minimap(framebuffer: &mut Framebuffer, maze: &Maze, player: &Player, angle: f32, minimap_x: usize, minimap_y: usize, scale: f32) {
     let mut d = 0.0;
     let max_distance = 50.0; 
 
+    // Avanza hasta la pared o el largo máximo y dibuja el rayo de una vez
+    let mut end = (player.pos.x as usize, player.pos.y as usize);
     while d < max_distance {
-        let cos = d * angle.cos();
-        let sin = d * angle.sin();
+        let x = (player.pos.x + d * angle.cos()) as usize;
+        let y = (player.pos.y + d * angle.sin()) as usize;
+        end = (x, y);
 
-        let x = (player.pos.x + cos) as usize;
-        let y = (player.pos.y + sin) as usize;
-
-        // Ajusta las coordenadas para el minimapa
-        let mx = minimap_x + (x as f32 * scale) as usize;
-        let my = minimap_y + (y as f32 * scale) as usize;
-
-        if mx >= framebuffer.width || my >= framebuffer.height {
-            break; // Evita dibujar fuera de los límites del framebuffer
-        }
-
-        framebuffer.set_current_color(0xFFFFFF);
-        framebuffer.point(mx, my);
-
-        let i = x / block_size;
-        let j = y / block_size;
-        if maze.get(j).and_then(|row| row.get(i)) == Some(&'#') { // '#' representa un muro
+        if maze.is_wall_cell(x / maze.block_size, y / maze.block_size) {
             break;
         }
         d += 1.0;
     }
+
+    // Ajusta las coordenadas para el minimapa
+    let to_minimap = |(x, y): (usize, usize)| (minimap_x as i32 + (x as f32 * scale) as i32, minimap_y as i32 + (y as f32 * scale) as i32);
+    let (x0, y0) = to_minimap((player.pos.x as usize, player.pos.y as usize));
+    let (x1, y1) = to_minimap(end);
+    framebuffer.line(x0, y0, x1, y1, 0xFFFFFF);
 }
 
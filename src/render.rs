@@ -0,0 +1,248 @@
+use crate::bitmap_font::{draw_bitmap_text, BitmapStyle};
+use crate::framebuffer::{Filter, Framebuffer};
+use crate::player::Player;
+use crate::maze::{Fog, Maze, DEFAULT_GOAL_TEXTURE, DEFAULT_WALL_TEXTURE};
+use crate::solver::Cell;
+use crate::ray_casting::{cast_ray, cast_ray_minimap};
+use crate::texture::Texture;
+
+use image::RgbaImage;
+use std::f32::consts::PI;
+
+// Texto con la fuente incorporada, sin escalar
+pub fn draw_text(framebuffer: &mut Framebuffer, text: &str, x: usize, y: usize, color: u32) {
+    draw_bitmap_text(framebuffer, text, x as i32, y as i32, &BitmapStyle { color, ..BitmapStyle::default() });
+}
+
+// Dibuja la imagen escalada por `scale` de modo que su punto `anchor` quede en (x, y).
+// `anchor` va en fracciones del tamaño de la imagen: (0, 0) es la esquina superior
+// izquierda y (0.5, 0.5) el centro. Lo que cae fuera del framebuffer se recorta.
+pub fn draw_image(framebuffer: &mut Framebuffer, image: &RgbaImage, x: i32, y: i32, scale: f32, anchor: (f32, f32)) {
+    let width = (image.width() as f32 * scale.max(0.0)).round() as usize;
+    let height = (image.height() as f32 * scale.max(0.0)).round() as usize;
+    let left = x - (anchor.0 * width as f32).round() as i32;
+    let top = y - (anchor.1 * height as f32).round() as i32;
+    framebuffer.blit(image, left, top, width, height, Filter::Bilinear);
+}
+
+pub fn cell_to_texture_color(cell: char, tx: u32, ty: u32, wall: &Texture, goal: &Texture) -> u32 {
+    let default_color = 0x000000;
+
+    match cell {
+        '+' => wall.get_pixel_color(tx, ty),
+        '-' => wall.get_pixel_color(tx, ty),
+        '|' => wall.get_pixel_color(tx, ty),
+        'g' => goal.get_pixel_color(tx, ty),
+        _ => default_color,
+    }
+}
+
+// Mezcla el color hacia el de la niebla según la distancia
+fn apply_fog(color: u32, distance: f32, fog: &Fog) -> u32 {
+    let t = (distance / fog.distance).clamp(0.0, 1.0);
+    let mix = |shift: u32| {
+        let c = ((color >> shift) & 0xFF) as f32;
+        let f = ((fog.color >> shift) & 0xFF) as f32;
+        ((c + (f - c) * t) as u32) << shift
+    };
+    mix(16) | mix(8) | mix(0)
+}
+
+fn cell_color(cell: char) -> u32 {
+    match cell {
+        '+' | '|' | '-' => 0x345f01,
+        'g' => 0xFF0000,
+        _ => 0x9fbf7a,
+    }
+}
+
+pub fn draw_cell(framebuffer: &mut Framebuffer, xo: usize, yo: usize, block_size: usize, cell: char) {
+    framebuffer.fill_rect(xo as i32, yo as i32, block_size, block_size, cell_color(cell));
+}
+
+pub fn render2d(framebuffer: &mut Framebuffer, player: &Player, maze: &Maze) {
+    let block_size = maze.block_size;
+
+    for (row, line) in maze.rows().enumerate() {
+        for (col, &cell) in line.iter().enumerate() {
+            draw_cell(framebuffer, col * block_size, row * block_size, block_size, cell);
+        }
+    }
+
+    framebuffer.set_current_color(0xFFFFFF);
+    framebuffer.point(player.pos.x as usize, player.pos.y as usize);
+
+    let num_rays = 100;
+
+    for i in 0..num_rays {
+        let current_ray = i as f32 / num_rays as f32;
+        let a = player.a - (player.fov / 2.0) + (player.fov * current_ray);
+        cast_ray(framebuffer, maze, player, a, block_size, true);
+    }
+}
+
+// Vista 2D del laberinto reducida para caber en width x height (sin deformarla).
+// Se dibuja directo a esa escala, un rectángulo por celda.
+pub fn render_thumbnail(maze: &Maze, width: usize, height: usize) -> Framebuffer {
+    let cell = (width as f32 / maze.width as f32).min(height as f32 / maze.height as f32);
+    let mut thumbnail = Framebuffer::new((maze.width as f32 * cell) as usize, (maze.height as f32 * cell) as usize);
+
+    // Los bordes se redondean desde el origen, así las celdas no dejan huecos entre sí
+    let edge = |index: usize| (index as f32 * cell) as i32;
+    for (row, line) in maze.rows().enumerate() {
+        for (col, &c) in line.iter().enumerate() {
+            let (x, y) = (edge(col), edge(row));
+            let (w, h) = ((edge(col + 1) - x) as usize, (edge(row + 1) - y) as usize);
+            thumbnail.fill_rect(x, y, w, h, cell_color(c));
+        }
+    }
+
+    // El punto de aparición con una línea hacia donde mira el jugador
+    let player = Player::at_spawn(maze);
+    let scale = cell / maze.block_size as f32;
+    let (x, y) = ((player.pos.x * scale) as i32, (player.pos.y * scale) as i32);
+    let reach = cell * 1.5;
+    let tip = ((x as f32 + player.a.cos() * reach) as i32, (y as f32 + player.a.sin() * reach) as i32);
+    thumbnail.line(x, y, tip.0, tip.1, 0xFFFFFF);
+    thumbnail.fill_circle(x, y, (cell / 4.0).max(1.0) as i32, 0xFFFFFF);
+
+    thumbnail
+}
+
+// Opciones de dibujo de la vista 3D que el jugador puede apagar
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct RenderOptions {
+    pub textures: bool, // Sin texturas las paredes son de un solo color, como en el mapa 2D
+    pub fog: bool,
+}
+
+impl Default for RenderOptions {
+    fn default() -> Self {
+        RenderOptions { textures: true, fog: true }
+    }
+}
+
+// Devuelve la distancia a la pared en cada columna, para dibujar encima sin atravesar paredes
+pub fn render3d(framebuffer: &mut Framebuffer, player: &Player, maze: &Maze, options: &RenderOptions) -> Vec<f32> {
+    let block_size = maze.block_size;
+    let hh = framebuffer.height as f32 / 2.0;
+    let num_rays = framebuffer.width;
+
+    let sky_color = maze.meta.sky_color;
+    let ground_color = maze.meta.floor_color;
+    let wall_texture = Texture::cached(&maze.meta.wall_texture, DEFAULT_WALL_TEXTURE);
+    let goal_texture = Texture::cached(&maze.meta.goal_texture, DEFAULT_GOAL_TEXTURE);
+
+    let (width, half) = (framebuffer.width, framebuffer.height / 2);
+    framebuffer.fill_rect(0, 0, width, half, sky_color);
+    framebuffer.fill_rect(0, half as i32, width, framebuffer.height - half, ground_color);
+
+    let mut depths = Vec::with_capacity(num_rays);
+
+    for i in 0..num_rays {
+        let current_ray = i as f32 / num_rays as f32;
+        let a = player.a - (player.fov / 2.0) + (player.fov * current_ray);
+        let intersect = cast_ray(framebuffer, maze, player, a, block_size, false);
+
+        let distance = intersect.distance * (a - player.a).cos();
+        depths.push(distance);
+        let stake_height = (framebuffer.height as f32 / distance) * 50.0;
+
+        let stake_top = (hh - (stake_height / 2.0)) as usize;
+        let stake_bottom = (hh + (stake_height / 2.0)) as usize;
+
+        for y in stake_top..stake_bottom {
+            let ty = (y as f32 - stake_top as f32) / (stake_bottom as f32 - stake_top as f32) * 128.0;
+            let tx = intersect.tx;
+
+            let mut color = if options.textures {
+                cell_to_texture_color(intersect.impact, tx as u32, ty as u32, &wall_texture, &goal_texture)
+            } else {
+                cell_color(intersect.impact)
+            };
+            if let Some(fog) = maze.meta.fog.as_ref().filter(|_| options.fog) {
+                color = apply_fog(color, distance, fog);
+            }
+            framebuffer.set_current_color(color);
+            framebuffer.point(i, y);
+        }
+    }
+
+    depths
+}
+
+// Migas de pan en el piso de la vista 3D, una por celda del camino
+pub fn render_floor_markers(framebuffer: &mut Framebuffer, player: &Player, maze: &Maze, path: &[Cell], depths: &[f32], color: u32) {
+    let hh = framebuffer.height as f32 / 2.0;
+
+    // Primero las más lejanas para que las cercanas queden encima
+    let mut markers: Vec<(f32, f32)> = path
+        .iter()
+        .filter_map(|&(col, row)| {
+            let offset = maze.cell_center(col, row) - player.pos;
+            let mut angle = offset.y.atan2(offset.x) - player.a;
+            angle = (angle + PI).rem_euclid(2.0 * PI) - PI;
+            let depth = offset.magnitude() * angle.cos();
+            (angle.abs() < player.fov / 2.0 && depth > 1.0).then_some((angle, depth))
+        })
+        .collect();
+    markers.sort_by(|a, b| b.1.total_cmp(&a.1));
+
+    framebuffer.set_current_color(color);
+    for (angle, depth) in markers {
+        // Misma proyección que las paredes: el piso a esa distancia está en hh + 25 * alto / distancia
+        let center_x = (angle + player.fov / 2.0) / player.fov * framebuffer.width as f32;
+        let center_y = hh + 25.0 * framebuffer.height as f32 / depth;
+        let radius = 4.0 * framebuffer.height as f32 / depth;
+
+        let x0 = (center_x - radius).max(0.0) as usize;
+        let x1 = ((center_x + radius) as usize).min(framebuffer.width.saturating_sub(1));
+        for x in x0..=x1 {
+            if depths.get(x).is_some_and(|&wall| wall < depth) {
+                continue;
+            }
+            // Elipse aplastada, como un disco visto sobre el piso
+            let dx = (x as f32 - center_x) / radius;
+            let half_height = (1.0 - dx * dx).max(0.0).sqrt() * radius * 0.4;
+            for y in (center_y - half_height) as usize..=(center_y + half_height) as usize {
+                framebuffer.point(x, y);
+            }
+        }
+    }
+}
+
+// Camino sugerido encima del minimapa
+pub fn render_minimap_path(framebuffer: &mut Framebuffer, maze: &Maze, path: &[Cell], minimap_x: usize, minimap_y: usize, minimap_scale: f32, color: u32) {
+    let block_size = (maze.block_size as f32 * minimap_scale) as usize;
+    let dot = (block_size / 2).max(1);
+
+    for &(col, row) in path {
+        let xo = minimap_x + col * block_size + (block_size - dot) / 2;
+        let yo = minimap_y + row * block_size + (block_size - dot) / 2;
+        framebuffer.fill_rect(xo as i32, yo as i32, dot, dot, color);
+    }
+}
+
+pub fn render_minimap(framebuffer: &mut Framebuffer, player: &Player, maze: &Maze, minimap_x: usize, minimap_y: usize, minimap_scale: f32) {
+    let block_size = (maze.block_size as f32 * minimap_scale) as usize;
+
+    for (row, line) in maze.rows().enumerate() {
+        for (col, &cell) in line.iter().enumerate() {
+            let xo = minimap_x + col * block_size;
+            let yo = minimap_y + row * block_size;
+            draw_cell(framebuffer, xo, yo, block_size, cell);
+        }
+    }
+
+    let player_x = minimap_x + (player.pos.x * minimap_scale) as usize;
+    let player_y = minimap_y + (player.pos.y * minimap_scale) as usize;
+    framebuffer.set_current_color(0xFF0000);
+    framebuffer.point(player_x, player_y);
+
+    let num_rays = 50;
+    for i in 0..num_rays {
+        let current_ray = i as f32 / num_rays as f32;
+        let angle = player.a - (player.fov / 2.0) + (player.fov * current_ray);
+        cast_ray_minimap(framebuffer, maze, player, angle, minimap_x, minimap_y, minimap_scale);
+    }
+}
@@ -0,0 +1,68 @@
+use std::fs;
+use std::io::{self, ErrorKind};
+
+use crate::player::InputFrame;
+
+// Cambia con cada formato; BRATREC2 guardaba el giro en píxeles del mouse y no en radianes
+const MAGIC: &[u8; 8] = b"BRATREC3";
+const FRAME_SIZE: usize = 10; // u16 de acciones + f32 del giro con el mouse + f32 del tiempo
+
+// Partida grabada: el nivel jugado y la entrada de cada cuadro. Con la misma
+// entrada la simulación repite exactamente el mismo recorrido.
+#[derive(Default)]
+pub struct Recording {
+    pub level: String, // Mismo formato que Level::path
+    pub frames: Vec<InputFrame>,
+}
+
+impl Recording {
+    pub fn new(level: &str) -> Self {
+        Recording { level: level.to_string(), frames: Vec::new() }
+    }
+
+    pub fn duration(&self) -> f32 {
+        self.frames.iter().map(|frame| frame.dt).sum()
+    }
+
+    // Formato binario: encabezado, largo y nombre del nivel, y luego un registro fijo por cuadro
+    pub fn save(&self, path: &str) -> io::Result<()> {
+        let mut bytes = Vec::with_capacity(MAGIC.len() + 4 + self.level.len() + self.frames.len() * FRAME_SIZE);
+        bytes.extend_from_slice(MAGIC);
+        bytes.extend_from_slice(&(self.level.len() as u32).to_le_bytes());
+        bytes.extend_from_slice(self.level.as_bytes());
+        for frame in &self.frames {
+            bytes.extend_from_slice(&frame.actions.to_le_bytes());
+            bytes.extend_from_slice(&frame.look.to_le_bytes());
+            bytes.extend_from_slice(&frame.dt.to_le_bytes());
+        }
+        fs::write(path, bytes)
+    }
+
+    pub fn load(path: &str) -> io::Result<Recording> {
+        let bytes = fs::read(path)?;
+        let invalid = |message: &str| io::Error::new(ErrorKind::InvalidData, format!("{}: {}", path, message));
+
+        let rest = bytes.strip_prefix(MAGIC).ok_or_else(|| invalid("no es una grabación"))?;
+        let (length, rest) = rest.split_first_chunk::<4>().ok_or_else(|| invalid("grabación incompleta"))?;
+        let length = u32::from_le_bytes(*length) as usize;
+        if rest.len() < length {
+            return Err(invalid("grabación incompleta"));
+        }
+        let (level, rest) = rest.split_at(length);
+        let level = String::from_utf8(level.to_vec()).map_err(|_| invalid("nombre de nivel inválido"))?;
+
+        if rest.len() % FRAME_SIZE != 0 {
+            return Err(invalid("grabación incompleta"));
+        }
+        let frames = rest
+            .chunks_exact(FRAME_SIZE)
+            .map(|chunk| InputFrame {
+                actions: u16::from_le_bytes([chunk[0], chunk[1]]),
+                look: f32::from_le_bytes([chunk[2], chunk[3], chunk[4], chunk[5]]),
+                dt: f32::from_le_bytes([chunk[6], chunk[7], chunk[8], chunk[9]]),
+            })
+            .collect();
+
+        Ok(Recording { level, frames })
+    }
+}
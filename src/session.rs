@@ -0,0 +1,256 @@
+use image::RgbaImage;
+
+use crate::daily::Date;
+use crate::framebuffer::Framebuffer;
+use crate::input::Action;
+use crate::levels::Level;
+use crate::maze::{Maze, MazeError};
+use crate::player::{apply_input, InputFrame, Player, TICK};
+use crate::render::{draw_image, render2d, render3d, render_floor_markers, render_minimap, render_minimap_path};
+use crate::replay::Recording;
+use crate::settings::Settings;
+use crate::solver::{shortest_path, Cell};
+use crate::text::{draw_string, Align, TextStyle};
+use crate::sounds::play_sound_effect;
+
+// Segundos que se muestra el camino después de pedir una pista
+const HINT_DURATION: f32 = 5.0;
+const HINT_COLOR: u32 = 0xFFD700;
+
+// Animación del screamer: de 0.1 a 1.5 veces su tamaño, creciendo 3 veces su tamaño por segundo
+const SCREAMER_START_SCALE: f32 = 0.1;
+const SCREAMER_END_SCALE: f32 = 1.5;
+const SCREAMER_GROWTH: f32 = 3.0;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Outcome {
+    Won,
+    OutOfTime,
+    ReplayEnded,
+}
+
+// Una partida en curso: la simulación del nivel y lo necesario para dibujarla
+pub struct Session {
+    pub path: String, // Level::path, para reiniciar el mismo nivel
+    pub daily: Option<Date>,
+    random: bool,
+    pub maze: Maze,
+    pub player: Player,
+    previous_player: Player,
+    map_view: bool,
+    map_key_held: bool,
+
+    // Tiempo de juego: suma del dt de cada paso, así una repetición da el mismo resultado
+    pub level_time: f32,
+    accumulator: f32,
+    pending_look: f32, // Giro del mouse de cuadros que todavía no dieron un paso
+    pub recording: Option<Recording>, // Solo si se pidió grabar la partida
+    replay_frames: Option<std::vec::IntoIter<InputFrame>>,
+
+    // Pistas: cuántas quedan, el camino mostrado y desde cuándo, y los segundos de castigo acumulados
+    hints_left: u32,
+    hint: Option<(Vec<Cell>, f32)>,
+    hint_key_held: bool,
+    penalty: f32,
+
+    screamer_image: Option<RgbaImage>,
+    screamer_scale: Option<f32>, // Tamaño del screamer mientras está en pantalla
+    last_screamer_time: f32,
+}
+
+impl Session {
+    pub fn new(level: Level, replay: Option<Recording>) -> Result<Session, MazeError> {
+        let maze = level.maze?;
+
+        // Sin la imagen del screamer el nivel se juega igual, solo que sin sustos
+        let screamer = &maze.meta.screamer;
+        let screamer_image = if screamer.enabled {
+            match image::open(&screamer.image) {
+                Ok(image) => Some(image.to_rgba8()),
+                Err(err) => {
+                    eprintln!("No se pudo abrir la imagen del screamer {}: {}", screamer.image, err);
+                    None
+                }
+            }
+        } else {
+            None
+        };
+
+        let player = Player::at_spawn(&maze);
+        Ok(Session {
+            recording: None,
+            path: level.path,
+            daily: level.daily,
+            random: level.random,
+            previous_player: player.clone(),
+            player,
+            map_view: false,
+            map_key_held: false,
+            level_time: 0.0,
+            accumulator: 0.0,
+            pending_look: 0.0,
+            replay_frames: replay.map(|recording| recording.frames.into_iter()),
+            hints_left: maze.meta.hints,
+            hint: None,
+            hint_key_held: false,
+            penalty: 0.0,
+            screamer_image,
+            screamer_scale: None,
+            last_screamer_time: 0.0,
+            maze,
+        })
+    }
+
+    // El mismo nivel desde el principio; el laberinto aleatorio se vuelve a generar
+    pub fn restart(&self) -> Result<Session, MazeError> {
+        let level = if self.random { Level::random() } else { Level::from_path(&self.path) };
+        Session::new(level, None)
+    }
+
+    // Desde ahora cada paso se guarda para poder repetir la partida
+    pub fn start_recording(&mut self) {
+        self.recording = Some(Recording::new(&self.path));
+    }
+
+    pub fn is_replay(&self) -> bool {
+        self.replay_frames.is_some()
+    }
+
+    // Tiempo final, con el castigo de las pistas
+    pub fn completion_time(&self) -> f32 {
+        self.level_time + self.penalty
+    }
+
+    // Avanza la simulación en pasos fijos de TICK segundos con el tiempo real del cuadro.
+    // La entrada viene de la ventana o, al repetir, de la grabación.
+    pub fn update(&mut self, live_input: InputFrame, frame_time: f32) -> Option<Outcome> {
+        self.accumulator += frame_time;
+        self.pending_look += live_input.look;
+
+        while self.accumulator >= TICK {
+            self.accumulator -= TICK;
+
+            let input = match &mut self.replay_frames {
+                Some(frames) => {
+                    let Some(frame) = frames.next() else {
+                        return Some(Outcome::ReplayEnded);
+                    };
+                    frame
+                }
+                None => InputFrame { dt: TICK, look: self.pending_look, ..live_input },
+            };
+            // El giro acumulado se aplica entero en el primer paso; sobre 60 FPS hay cuadros sin
+            // ningún paso y su giro espera al siguiente
+            self.pending_look = 0.0;
+
+            if let Some(outcome) = self.step(&input) {
+                return Some(outcome);
+            }
+        }
+
+        if self.replay_frames.as_ref().is_some_and(|frames| frames.len() == 0) {
+            return Some(Outcome::ReplayEnded);
+        }
+        None
+    }
+
+    fn step(&mut self, input: &InputFrame) -> Option<Outcome> {
+        if let Some(recording) = &mut self.recording {
+            recording.frames.push(*input);
+        }
+        self.level_time += input.dt;
+
+        let map_key_down = input.is_down(Action::ToggleMap);
+        if map_key_down && !self.map_key_held {
+            self.map_view = !self.map_view;
+        }
+        self.map_key_held = map_key_down;
+
+        self.previous_player = self.player.clone();
+        apply_input(&mut self.player, &self.maze, input);
+
+        let cell = ((self.player.pos.x as usize) / self.maze.block_size, (self.player.pos.y as usize) / self.maze.block_size);
+
+        // Una pista por pulsación, mientras queden
+        let hint_key_down = input.is_down(Action::Hint);
+        if hint_key_down && !self.hint_key_held && self.hints_left > 0 {
+            if let Some(path) = shortest_path(&self.maze, cell) {
+                self.hints_left -= 1;
+                self.penalty += self.maze.meta.hint_penalty;
+                self.hint = Some((path, self.level_time));
+            }
+        }
+        self.hint_key_held = hint_key_down;
+        if self.hint.as_ref().is_some_and(|(_, shown)| self.level_time - shown >= HINT_DURATION) {
+            self.hint = None;
+        }
+
+        // Verificar si el screamer debe activarse según el intervalo del nivel
+        let screamer = &self.maze.meta.screamer;
+        if self.screamer_image.is_some() && self.screamer_scale.is_none() && self.level_time - self.last_screamer_time >= screamer.interval {
+            self.screamer_scale = Some(SCREAMER_START_SCALE);
+            self.last_screamer_time = self.level_time; // Resetear el temporizador
+            play_sound_effect(&screamer.sound); // Reproducir sonido del screamer al mismo tiempo que la imagen aparece
+        }
+
+        // Si el jugador está en la meta o en una celda adyacente (diagonal incluida)
+        if self.maze.is_near_goal(cell.0, cell.1) {
+            return Some(Outcome::Won);
+        }
+
+        // Sin tiempo la partida termina sin victoria
+        if self.maze.meta.time_limit.is_some_and(|time_limit| self.completion_time() >= time_limit) {
+            return Some(Outcome::OutOfTime);
+        }
+        None
+    }
+
+    pub fn draw(&mut self, framebuffer: &mut Framebuffer, frame_time: f32, settings: &Settings) {
+        // Se dibuja entre el paso anterior y el actual según el tiempo que sobró
+        let mut view = self.previous_player.interpolate(&self.player, self.accumulator / TICK);
+        view.fov = settings.fov_radians();
+        let maze = &self.maze;
+
+        framebuffer.clear();
+
+        if self.map_view {
+            render2d(framebuffer, &view, maze);
+        } else {
+            let depths = render3d(framebuffer, &view, maze, &settings.render_options());
+            if let Some((path, _)) = &self.hint {
+                render_floor_markers(framebuffer, &view, maze, path, &depths, HINT_COLOR);
+            }
+        }
+
+        // El minimapa ocupa a lo sumo esa fracción de la pantalla; los laberintos grandes se reducen más
+        let minimap_width = (framebuffer.width as f32 * settings.minimap_scale) as usize;
+        let minimap_height = (framebuffer.height as f32 * settings.minimap_scale) as usize;
+        let minimap_scale = (minimap_width as f32 / (maze.width * maze.block_size) as f32)
+            .min(minimap_height as f32 / (maze.height * maze.block_size) as f32)
+            .min(settings.minimap_scale);
+        let minimap_x = framebuffer.width - minimap_width - 20;
+        let minimap_y = framebuffer.height - minimap_height - 20;
+
+        render_minimap(framebuffer, &view, maze, minimap_x, minimap_y, minimap_scale);
+        if let Some((path, _)) = &self.hint {
+            render_minimap_path(framebuffer, maze, path, minimap_x, minimap_y, minimap_scale, HINT_COLOR);
+        }
+        let style = TextStyle { color: HINT_COLOR, ..TextStyle::default() };
+        draw_string(framebuffer, &format!("Pistas: {}", self.hints_left), minimap_x as i32, minimap_y as i32 - 26, &style);
+
+        // Dibujar el screamer si se activó; crece desde el centro a velocidad constante sin importar los FPS
+        if let (Some(scale), Some(image)) = (self.screamer_scale, &self.screamer_image) {
+            let (center_x, center_y) = (framebuffer.width as i32 / 2, framebuffer.height as i32 / 2);
+            draw_image(framebuffer, image, center_x, center_y, scale, (0.5, 0.5));
+            let scale = scale + SCREAMER_GROWTH * frame_time;
+            self.screamer_scale = (scale < SCREAMER_END_SCALE).then_some(scale); // Ocultar el screamer al llegar a su tamaño final
+        }
+
+        // Tiempo restante si el nivel tiene límite
+        if let Some(time_limit) = maze.meta.time_limit {
+            let remaining = time_limit - self.completion_time();
+            let style = TextStyle { size: 32.0, align: Align::Center, ..TextStyle::default() };
+            draw_string(framebuffer, &format!("{}", remaining.max(0.0).ceil() as u32), framebuffer.width as i32 / 2, 10, &style);
+        }
+    }
+}
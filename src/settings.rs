@@ -0,0 +1,165 @@
+use std::fs;
+use std::io;
+
+use crate::input::{config_dir, DEFAULT_MOUSE_SENSITIVITY};
+use crate::render::RenderOptions;
+
+pub const SETTINGS_FILE: &str = "settings.txt";
+
+// Resoluciones que ofrece la pantalla de configuración; en el archivo se acepta cualquiera
+pub const RESOLUTIONS: [(usize, usize); 4] = [(1024, 768), (1300, 900), (1600, 900), (1920, 1080)];
+const MIN_RESOLUTION: (usize, usize) = (320, 240);
+
+// Límites de cada valor, también los de la pantalla de configuración
+pub const FOV_RANGE: (f32, f32) = (30.0, 120.0); // Grados
+pub const SENSITIVITY_RANGE: (f32, f32) = (0.001, 0.02); // Radianes por píxel
+pub const SMOOTHING_RANGE: (f32, f32) = (0.0, 0.9);
+pub const VOLUME_RANGE: (f32, f32) = (0.0, 1.0);
+pub const MINIMAP_RANGE: (f32, f32) = (0.1, 0.4); // Fracción de la pantalla
+
+// Opciones del jugador, guardadas en la carpeta de configuración
+#[derive(Clone, Debug, PartialEq)]
+pub struct Settings {
+    pub width: usize,
+    pub height: usize,
+    pub fov: f32, // Grados
+    pub mouse_sensitivity: f32,
+    pub invert_mouse: bool,
+    pub mouse_smoothing: f32,
+    pub music_volume: f32,
+    pub effects_volume: f32,
+    pub minimap_scale: f32,
+    pub textures: bool,
+    pub fog: bool,
+    pub show_fps: bool,
+}
+
+impl Default for Settings {
+    fn default() -> Self {
+        Settings {
+            width: 1300,
+            height: 900,
+            fov: 45.0,
+            mouse_sensitivity: DEFAULT_MOUSE_SENSITIVITY,
+            invert_mouse: false,
+            mouse_smoothing: 0.0,
+            music_volume: 1.0,
+            effects_volume: 1.0,
+            minimap_scale: 0.2,
+            textures: true,
+            fog: true,
+            show_fps: true,
+        }
+    }
+}
+
+impl Settings {
+    pub fn fov_radians(&self) -> f32 {
+        self.fov.to_radians()
+    }
+
+    pub fn render_options(&self) -> RenderOptions {
+        RenderOptions { textures: self.textures, fog: self.fog }
+    }
+
+    // Líneas "clave: valor"; las claves que faltan o tienen un valor inválido usan el valor por defecto.
+    // Devuelve también los problemas encontrados, uno por línea ignorada.
+    pub fn parse(text: &str) -> (Settings, Vec<String>) {
+        let mut settings = Settings::default();
+        let mut problems = Vec::new();
+
+        for (index, raw) in text.lines().enumerate() {
+            let line = index + 1;
+            let raw = raw.trim();
+            if raw.is_empty() || raw.starts_with('#') {
+                continue;
+            }
+
+            let Some((key, value)) = raw.split_once(':') else {
+                problems.push(format!("línea {}: se esperaba \"clave: valor\"", line));
+                continue;
+            };
+            let (key, value) = (key.trim(), value.trim());
+
+            let valid = match key {
+                "resolution" => parse_resolution(value).map(|(width, height)| (settings.width, settings.height) = (width, height)),
+                "fov" => parse_in_range(value, FOV_RANGE).map(|fov| settings.fov = fov),
+                "mouse_sensitivity" => parse_in_range(value, SENSITIVITY_RANGE).map(|value| settings.mouse_sensitivity = value),
+                "invert_mouse" => parse_bool(value).map(|value| settings.invert_mouse = value),
+                "mouse_smoothing" => parse_in_range(value, SMOOTHING_RANGE).map(|value| settings.mouse_smoothing = value),
+                "music_volume" => parse_in_range(value, VOLUME_RANGE).map(|value| settings.music_volume = value),
+                "effects_volume" => parse_in_range(value, VOLUME_RANGE).map(|value| settings.effects_volume = value),
+                "minimap_scale" => parse_in_range(value, MINIMAP_RANGE).map(|value| settings.minimap_scale = value),
+                "textures" => parse_bool(value).map(|value| settings.textures = value),
+                "fog" => parse_bool(value).map(|value| settings.fog = value),
+                "show_fps" => parse_bool(value).map(|value| settings.show_fps = value),
+                _ => {
+                    problems.push(format!("línea {}: clave desconocida {:?}", line, key));
+                    continue;
+                }
+            };
+            if valid.is_none() {
+                problems.push(format!("línea {}: valor inválido para {}: {:?}", line, key, value));
+            }
+        }
+
+        (settings, problems)
+    }
+
+    pub fn to_text(&self) -> String {
+        let mut text = String::from("# clave: valor\n");
+        text.push_str(&format!("resolution: {}x{}\n", self.width, self.height));
+        text.push_str(&format!("fov: {}\n", self.fov));
+        text.push_str(&format!("mouse_sensitivity: {}\n", self.mouse_sensitivity));
+        text.push_str(&format!("invert_mouse: {}\n", self.invert_mouse));
+        text.push_str(&format!("mouse_smoothing: {}\n", self.mouse_smoothing));
+        text.push_str(&format!("music_volume: {}\n", self.music_volume));
+        text.push_str(&format!("effects_volume: {}\n", self.effects_volume));
+        text.push_str(&format!("minimap_scale: {}\n", self.minimap_scale));
+        text.push_str(&format!("textures: {}\n", self.textures));
+        text.push_str(&format!("fog: {}\n", self.fog));
+        text.push_str(&format!("show_fps: {}\n", self.show_fps));
+        text
+    }
+
+    // Sin archivo se usan los valores por defecto
+    pub fn load() -> Settings {
+        let Some(path) = config_dir().map(|dir| dir.join(SETTINGS_FILE)) else {
+            return Settings::default();
+        };
+        let Ok(text) = fs::read_to_string(&path) else {
+            return Settings::default();
+        };
+
+        let (settings, problems) = Settings::parse(&text);
+        for problem in problems {
+            eprintln!("{}: {}", path.display(), problem);
+        }
+        settings
+    }
+
+    pub fn save(&self) -> io::Result<()> {
+        let dir = config_dir().ok_or_else(|| io::Error::new(io::ErrorKind::NotFound, "no hay carpeta de configuración"))?;
+        fs::create_dir_all(&dir)?;
+        fs::write(dir.join(SETTINGS_FILE), self.to_text())
+    }
+}
+
+// "1300x900"
+pub fn parse_resolution(value: &str) -> Option<(usize, usize)> {
+    let (width, height) = value.split_once('x')?;
+    let (width, height) = (width.trim().parse().ok()?, height.trim().parse().ok()?);
+    (width >= MIN_RESOLUTION.0 && height >= MIN_RESOLUTION.1).then_some((width, height))
+}
+
+fn parse_in_range(value: &str, (min, max): (f32, f32)) -> Option<f32> {
+    value.parse::<f32>().ok().filter(|number| (min..=max).contains(number))
+}
+
+fn parse_bool(value: &str) -> Option<bool> {
+    match value {
+        "true" | "on" | "yes" => Some(true),
+        "false" | "off" | "no" => Some(false),
+        _ => None,
+    }
+}
@@ -0,0 +1,262 @@
+use std::cmp::Reverse;
+use std::collections::BinaryHeap;
+use std::fmt;
+
+use crate::maze::Maze;
+
+pub type Cell = (usize, usize); // (columna, fila)
+
+// Caracteres libres vecinos (arriba, derecha, abajo, izquierda); el jugador y el bot se mueven en esta grilla
+pub fn open_neighbors(maze: &Maze, (col, row): Cell) -> impl Iterator<Item = Cell> + '_ {
+    let candidates = [
+        row.checked_sub(1).map(|row| (col, row)),
+        Some((col + 1, row)),
+        Some((col, row + 1)),
+        col.checked_sub(1).map(|col| (col, row)),
+    ];
+    candidates.into_iter().flatten().filter(|&(col, row)| !maze.is_wall_cell(col, row))
+}
+
+// Camino más corto (A*) desde `from` hasta una celda desde donde se gana, incluyendo ambos extremos
+pub fn shortest_path(maze: &Maze, from: Cell) -> Option<Vec<Cell>> {
+    if maze.is_wall_cell(from.0, from.1) {
+        return None;
+    }
+
+    let index = |(col, row): Cell| row * maze.width + col;
+    // Distancia Manhattan al anillo alrededor de la meta; nunca sobreestima
+    let heuristic = |(col, row): Cell| {
+        let (goal_col, goal_row) = maze.goal;
+        col.abs_diff(goal_col).saturating_sub(1) + row.abs_diff(goal_row).saturating_sub(1)
+    };
+
+    let mut cost = vec![usize::MAX; maze.width * maze.height];
+    let mut came_from: Vec<Option<Cell>> = vec![None; maze.width * maze.height];
+    let mut open = BinaryHeap::new();
+
+    cost[index(from)] = 0;
+    open.push(Reverse((heuristic(from), 0, from)));
+
+    while let Some(Reverse((_, current_cost, cell))) = open.pop() {
+        if current_cost > cost[index(cell)] {
+            continue;
+        }
+
+        if maze.is_near_goal(cell.0, cell.1) {
+            let mut path = vec![cell];
+            let mut current = cell;
+            while let Some(previous) = came_from[index(current)] {
+                path.push(previous);
+                current = previous;
+            }
+            path.reverse();
+            return Some(path);
+        }
+
+        for next in open_neighbors(maze, cell) {
+            let next_cost = current_cost + 1;
+            if next_cost < cost[index(next)] {
+                cost[index(next)] = next_cost;
+                came_from[index(next)] = Some(cell);
+                open.push(Reverse((next_cost + heuristic(next), next_cost, next)));
+            }
+        }
+    }
+
+    None
+}
+
+// Métricas de dificultad de un laberinto
+pub struct Analysis {
+    pub solvable: bool,
+    pub path: Vec<Cell>,
+    pub open_cells: usize,
+    pub reachable_cells: usize,
+    pub dead_ends: usize,
+    pub junctions: usize,
+    pub turns: usize,
+}
+
+impl Analysis {
+    // Pasos desde la aparición hasta la meta
+    pub fn path_length(&self) -> usize {
+        self.path.len().saturating_sub(1)
+    }
+}
+
+// Celdas lógicas del laberinto. En los dibujados con "+--+" los pasillos miden 2 caracteres
+// y las paredes 1, así que cada celda ocupa 3 columnas por 2 filas de caracteres; los que no
+// siguen esa retícula se miden carácter por carácter.
+pub struct CellGraph<'a> {
+    maze: &'a Maze,
+    lattice: bool,
+    pub width: usize,
+    pub height: usize,
+}
+
+impl<'a> CellGraph<'a> {
+    pub fn new(maze: &'a Maze) -> CellGraph<'a> {
+        let lattice = maze.width >= 4
+            && maze.height >= 3
+            && (maze.width - 1).is_multiple_of(3)
+            && (maze.height - 1).is_multiple_of(2)
+            && (0..maze.height).step_by(2).all(|row| (0..maze.width).step_by(3).all(|col| maze.cell(col, row) == Some('+')));
+
+        if lattice {
+            CellGraph { maze, lattice, width: (maze.width - 1) / 3, height: (maze.height - 1) / 2 }
+        } else {
+            CellGraph { maze, lattice, width: maze.width, height: maze.height }
+        }
+    }
+
+    // Celda lógica que contiene el carácter (col, row)
+    pub fn cell_at(&self, (col, row): Cell) -> Cell {
+        if self.lattice {
+            ((col.saturating_sub(1) / 3).min(self.width - 1), (row.saturating_sub(1) / 2).min(self.height - 1))
+        } else {
+            (col, row)
+        }
+    }
+
+    // Columnas y fila de caracteres del interior de una celda
+    fn chars(&self, (col, row): Cell) -> (std::ops::Range<usize>, usize) {
+        if self.lattice {
+            (3 * col + 1..3 * col + 3, 2 * row + 1)
+        } else {
+            (col..col + 1, row)
+        }
+    }
+
+    pub fn is_open(&self, cell: Cell) -> bool {
+        let (cols, row) = self.chars(cell);
+        cols.into_iter().any(|col| !self.maze.is_wall_cell(col, row))
+    }
+
+    // Se gana en cualquier celda con un carácter junto a la meta
+    pub fn is_goal(&self, cell: Cell) -> bool {
+        let (mut cols, row) = self.chars(cell);
+        cols.any(|col| self.maze.is_near_goal(col, row))
+    }
+
+    // Entre dos celdas vecinas de la retícula se pasa si la pared que las separa está abierta
+    fn is_passage(&self, (col, row): Cell, (next_col, next_row): Cell) -> bool {
+        if !self.lattice {
+            return true;
+        }
+        if row == next_row {
+            let wall = 3 * col.max(next_col);
+            !self.maze.is_wall_cell(wall, 2 * row + 1)
+        } else {
+            let wall = 2 * row.max(next_row);
+            (3 * col + 1..3 * col + 3).all(|x| !self.maze.is_wall_cell(x, wall))
+        }
+    }
+
+    // Celdas vecinas a las que se puede pasar (arriba, derecha, abajo, izquierda)
+    pub fn neighbors(&self, (col, row): Cell) -> impl Iterator<Item = Cell> + '_ {
+        let candidates = [
+            row.checked_sub(1).map(|row| (col, row)),
+            Some((col + 1, row)).filter(|&(col, _)| col < self.width),
+            Some((col, row + 1)).filter(|&(_, row)| row < self.height),
+            col.checked_sub(1).map(|col| (col, row)),
+        ];
+        candidates
+            .into_iter()
+            .flatten()
+            .filter(move |&next| self.is_open(next) && self.is_passage((col, row), next))
+    }
+
+    pub fn cells(&self) -> impl Iterator<Item = Cell> + '_ {
+        (0..self.height).flat_map(move |row| (0..self.width).map(move |col| (col, row)))
+    }
+}
+
+// Búsqueda en anchura sobre las celdas lógicas: las celdas alcanzables en orden de distancia
+// y de dónde se llegó a cada una
+fn search(graph: &CellGraph, from: Cell) -> (Vec<Cell>, Vec<Option<Cell>>) {
+    let index = |(col, row): Cell| row * graph.width + col;
+    let mut came_from = vec![None; graph.width * graph.height];
+    if !graph.is_open(from) {
+        return (Vec::new(), came_from);
+    }
+    came_from[index(from)] = Some(from);
+
+    let mut order = vec![from];
+    let mut next_index = 0;
+    while let Some(&cell) = order.get(next_index) {
+        next_index += 1;
+        for next in graph.neighbors(cell) {
+            if came_from[index(next)].is_none() {
+                came_from[index(next)] = Some(cell);
+                order.push(next);
+            }
+        }
+    }
+    (order, came_from)
+}
+
+// Las métricas se cuentan en celdas lógicas, no en caracteres
+pub fn analyze(maze: &Maze) -> Analysis {
+    let graph = CellGraph::new(maze);
+    let index = |(col, row): Cell| row * graph.width + col;
+    let spawn = graph.cell_at(maze.spawn);
+    let (reached, came_from) = search(&graph, spawn);
+
+    let mut open_cells = 0;
+    let mut dead_ends = 0;
+    let mut junctions = 0;
+    for cell in graph.cells().filter(|&cell| graph.is_open(cell)) {
+        open_cells += 1;
+        match graph.neighbors(cell).count() {
+            1 => dead_ends += 1,
+            3.. => junctions += 1,
+            _ => {}
+        }
+    }
+
+    // La búsqueda en anchura llega primero a la celda de meta más cercana
+    let path = reached.iter().find(|&&cell| graph.is_goal(cell)).map(|&goal| {
+        let mut path = vec![goal];
+        let mut current = goal;
+        while current != spawn {
+            current = came_from[index(current)].unwrap();
+            path.push(current);
+        }
+        path.reverse();
+        path
+    });
+
+    // Cambios de dirección a lo largo del camino más corto
+    let turns = path.as_ref().map_or(0, |path| {
+        path.windows(3)
+            .filter(|step| {
+                let first = (step[1].0 as isize - step[0].0 as isize, step[1].1 as isize - step[0].1 as isize);
+                let second = (step[2].0 as isize - step[1].0 as isize, step[2].1 as isize - step[1].1 as isize);
+                first != second
+            })
+            .count()
+    });
+
+    Analysis {
+        solvable: path.is_some(),
+        path: path.unwrap_or_default(),
+        open_cells,
+        reachable_cells: reached.len(),
+        dead_ends,
+        junctions,
+        turns,
+    }
+}
+
+impl fmt::Display for Analysis {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        if self.solvable {
+            writeln!(f, "meta alcanzable: sí")?;
+            writeln!(f, "camino más corto: {} celdas, {} giros", self.path_length(), self.turns)?;
+        } else {
+            writeln!(f, "meta alcanzable: no")?;
+        }
+        writeln!(f, "celdas libres: {} ({} alcanzables)", self.open_cells, self.reachable_cells)?;
+        write!(f, "callejones sin salida: {}, cruces: {}", self.dead_ends, self.junctions)
+    }
+}
@@ -1,6 +1,7 @@
 use rodio::{OutputStream, Sink, Decoder};
 use std::fs::File;
 use std::io::BufReader;
+use std::sync::atomic::{AtomicBool, AtomicU32, AtomicU64, Ordering};
 use std::sync::{Arc, Mutex};
 use std::thread;
 use once_cell::sync::Lazy;
@@ -8,18 +9,60 @@ use once_cell::sync::Lazy;
 // Variable global para el Sink, lo cual nos permite detener la música.
 static CURRENT_SINK: Lazy<Arc<Mutex<Option<Arc<Sink>>>>> = Lazy::new(|| Arc::new(Mutex::new(None)));
 
-pub fn play_background_music(file_path: &'static str) {
+// Cada cambio de música incrementa el contador; un hilo que termina de abrir su
+// archivo después de otro cambio ya no debe sonar.
+static MUSIC_GENERATION: AtomicU64 = AtomicU64::new(0);
+
+// Con --no-audio no se abre ningún dispositivo de sonido
+static AUDIO_ENABLED: AtomicBool = AtomicBool::new(true);
+
+pub fn disable_audio() {
+    AUDIO_ENABLED.store(false, Ordering::SeqCst);
+}
+
+// Volúmenes de 0 a 1, guardados como los bits del f32
+static MUSIC_VOLUME: AtomicU32 = AtomicU32::new(0x3F80_0000); // 1.0
+static EFFECTS_VOLUME: AtomicU32 = AtomicU32::new(0x3F80_0000);
+
+fn volume(setting: &AtomicU32) -> f32 {
+    f32::from_bits(setting.load(Ordering::SeqCst))
+}
+
+// La música que ya suena cambia de volumen enseguida; los efectos, desde el próximo
+pub fn set_volumes(music: f32, effects: f32) {
+    MUSIC_VOLUME.store(music.to_bits(), Ordering::SeqCst);
+    EFFECTS_VOLUME.store(effects.to_bits(), Ordering::SeqCst);
+    if let Some(sink) = CURRENT_SINK.lock().unwrap().as_ref() {
+        sink.set_volume(music);
+    }
+}
+
+pub fn play_background_music(file_path: &str) {
+    if !AUDIO_ENABLED.load(Ordering::SeqCst) {
+        return;
+    }
     let file_path_clone = file_path.to_string(); // Clonamos el path
+    let generation = MUSIC_GENERATION.fetch_add(1, Ordering::SeqCst) + 1;
     thread::spawn(move || {
-        let (_stream, handle) = OutputStream::try_default().unwrap();
-        let sink = Arc::new(Sink::try_new(&handle).unwrap()); // Crear Arc de Sink
-        let file = BufReader::new(File::open(file_path_clone).unwrap());
-        let source = Decoder::new(file).unwrap();
+        // Sin dispositivo de audio (por ejemplo por SSH) el juego sigue sin sonido
+        let Ok((_stream, handle)) = OutputStream::try_default() else { return };
+        let Ok(sink) = Sink::try_new(&handle) else { return };
+        sink.set_volume(volume(&MUSIC_VOLUME));
+        let sink = Arc::new(sink); // Crear Arc de Sink
+        // Un archivo que no existe o no se puede decodificar simplemente no suena
+        let Ok(file) = File::open(file_path_clone) else { return };
+        let Ok(source) = Decoder::new(BufReader::new(file)) else { return };
         
         // Guardamos la referencia del Sink en la variable global
         {
             let mut current_sink = CURRENT_SINK.lock().unwrap();
-            *current_sink = Some(Arc::clone(&sink)); // Usamos Arc::clone
+            if MUSIC_GENERATION.load(Ordering::SeqCst) != generation {
+                return;
+            }
+            // La música anterior se detiene al empezar la nueva
+            if let Some(previous) = current_sink.replace(Arc::clone(&sink)) {
+                previous.stop();
+            }
         }
 
         sink.append(source);
@@ -28,19 +71,37 @@ pub fn play_background_music(file_path: &'static str) {
 }
 
 pub fn stop_music() {
+    MUSIC_GENERATION.fetch_add(1, Ordering::SeqCst);
     let mut current_sink = CURRENT_SINK.lock().unwrap();
     if let Some(sink) = current_sink.take() {
         sink.stop(); // Detenemos la música
     }
 }
 
+// Pausa la música sin perder por dónde iba, por ejemplo durante el menú de pausa
+pub fn pause_music() {
+    if let Some(sink) = CURRENT_SINK.lock().unwrap().as_ref() {
+        sink.pause();
+    }
+}
+
+pub fn resume_music() {
+    if let Some(sink) = CURRENT_SINK.lock().unwrap().as_ref() {
+        sink.play();
+    }
+}
+
 pub fn play_sound_effect(file_path: &str) {
+    if !AUDIO_ENABLED.load(Ordering::SeqCst) {
+        return;
+    }
     let file_path_clone = file_path.to_string(); // Clonar el path para evitar problemas de lifetime
     thread::spawn(move || {
-        let (_stream, handle) = OutputStream::try_default().unwrap();
-        let sink = Sink::try_new(&handle).unwrap();
-        let file = BufReader::new(File::open(file_path_clone).unwrap());
-        let source = Decoder::new(file).unwrap();
+        let Ok((_stream, handle)) = OutputStream::try_default() else { return };
+        let Ok(sink) = Sink::try_new(&handle) else { return };
+        sink.set_volume(volume(&EFFECTS_VOLUME));
+        let Ok(file) = File::open(file_path_clone) else { return };
+        let Ok(source) = Decoder::new(BufReader::new(file)) else { return };
         sink.append(source);
         sink.sleep_until_end();
     });
@@ -0,0 +1,305 @@
+use std::collections::HashMap;
+use std::io::{stdout, Write};
+use std::time::{Duration, Instant};
+
+use crossterm::cursor::{Hide, MoveTo, Show};
+use crossterm::event::{
+    self, Event, KeyCode, KeyEventKind, KeyModifiers, KeyboardEnhancementFlags, ModifierKeyCode,
+    PopKeyboardEnhancementFlags, PushKeyboardEnhancementFlags,
+};
+use crossterm::style::{Color, Print, ResetColor, SetBackgroundColor, SetForegroundColor};
+use crossterm::terminal::{
+    self, Clear, ClearType, EnterAlternateScreen, LeaveAlternateScreen, SetTitle,
+};
+use crossterm::{execute, queue};
+use minifb::Key;
+
+use crate::backend::Backend;
+use crate::framebuffer::Framebuffer;
+
+// Sin eventos de "soltar tecla", una tecla se considera presionada durante este tiempo.
+const KEY_HOLD: Duration = Duration::from_millis(150);
+// Limita los cuadros enviados a la terminal para no saturar conexiones SSH.
+const MIN_FRAME_TIME: Duration = Duration::from_millis(33);
+
+// Dibuja el framebuffer con caracteres de medio bloque (dos píxeles por celda)
+// y colores ANSI de 24 bits, leyendo el teclado desde la TTY.
+pub struct TerminalBackend {
+    open: bool,
+    keys: HashMap<Key, Instant>,
+    release_events: bool,
+    cells: Vec<(u32, u32)>,
+    columns: usize,
+    rows: usize,
+    last_frame: Instant,
+}
+
+impl TerminalBackend {
+    pub fn new(title: &str) -> Self {
+        terminal::enable_raw_mode().unwrap();
+        let mut out = stdout();
+        execute!(out, EnterAlternateScreen, Hide, SetTitle(title), Clear(ClearType::All)).unwrap();
+
+        // Las terminales que lo soportan reportan cuándo se suelta una tecla,
+        // y también Shift, Ctrl y Alt solos para poder asignarlos.
+        let release_events = terminal::supports_keyboard_enhancement().unwrap_or(false);
+        if release_events {
+            let flags = KeyboardEnhancementFlags::REPORT_EVENT_TYPES | KeyboardEnhancementFlags::REPORT_ALL_KEYS_AS_ESCAPE_CODES;
+            execute!(out, PushKeyboardEnhancementFlags(flags)).unwrap();
+        }
+
+        TerminalBackend {
+            open: true,
+            keys: HashMap::new(),
+            release_events,
+            cells: Vec::new(),
+            columns: 0,
+            rows: 0,
+            last_frame: Instant::now(),
+        }
+    }
+
+    fn read_input(&mut self) {
+        while event::poll(Duration::ZERO).unwrap_or(false) {
+            let Ok(Event::Key(key_event)) = event::read() else {
+                continue;
+            };
+
+            if key_event.code == KeyCode::Char('c') && key_event.modifiers.contains(KeyModifiers::CONTROL) {
+                self.open = false;
+                continue;
+            }
+
+            if let Some(key) = map_key(key_event.code) {
+                if key_event.kind == KeyEventKind::Release {
+                    self.keys.remove(&key);
+                } else {
+                    self.keys.insert(key, Instant::now());
+                }
+            }
+        }
+    }
+
+    fn draw(&mut self, framebuffer: &Framebuffer) {
+        let (columns, rows) = terminal::size().unwrap_or((80, 24));
+        let (columns, rows) = (columns as usize, rows as usize);
+        let mut out = stdout().lock();
+
+        if columns != self.columns || rows != self.rows {
+            self.columns = columns;
+            self.rows = rows;
+            self.cells = vec![(u32::MAX, u32::MAX); columns * rows];
+            queue!(out, ResetColor, Clear(ClearType::All)).unwrap();
+        }
+
+        let pixel_rows = rows * 2;
+        let mut last_colors = None;
+
+        for row in 0..rows {
+            let mut cursor_in_place = false;
+            for col in 0..columns {
+                let top = sample_area(framebuffer, col, row * 2, columns, pixel_rows);
+                let bottom = sample_area(framebuffer, col, row * 2 + 1, columns, pixel_rows);
+
+                let index = row * columns + col;
+                if self.cells[index] == (top, bottom) {
+                    cursor_in_place = false;
+                    continue;
+                }
+                self.cells[index] = (top, bottom);
+
+                if !cursor_in_place {
+                    queue!(out, MoveTo(col as u16, row as u16)).unwrap();
+                }
+                if last_colors != Some((top, bottom)) {
+                    queue!(out, SetForegroundColor(to_color(top)), SetBackgroundColor(to_color(bottom))).unwrap();
+                    last_colors = Some((top, bottom));
+                }
+                queue!(out, Print('▀')).unwrap();
+                cursor_in_place = true;
+            }
+        }
+
+        out.flush().unwrap();
+    }
+}
+
+impl Backend for TerminalBackend {
+    fn is_open(&self) -> bool {
+        self.open
+    }
+
+    fn is_key_down(&self, key: Key) -> bool {
+        match self.keys.get(&key) {
+            Some(_) if self.release_events => true,
+            Some(pressed) => pressed.elapsed() < KEY_HOLD,
+            None => false,
+        }
+    }
+
+    fn get_mouse_pos(&self) -> Option<(f32, f32)> {
+        None
+    }
+
+    fn is_mouse_down(&self) -> bool {
+        false
+    }
+
+    fn set_cursor_visibility(&mut self, _visible: bool) {}
+
+    fn warp_mouse(&self, _x: f32, _y: f32) -> bool {
+        false
+    }
+
+    fn update(&mut self, framebuffer: &Framebuffer) {
+        let elapsed = self.last_frame.elapsed();
+        if elapsed < MIN_FRAME_TIME {
+            std::thread::sleep(MIN_FRAME_TIME - elapsed);
+        }
+        self.last_frame = Instant::now();
+
+        self.draw(framebuffer);
+        self.read_input();
+    }
+}
+
+impl Drop for TerminalBackend {
+    fn drop(&mut self) {
+        let mut out = stdout();
+        if self.release_events {
+            let _ = execute!(out, PopKeyboardEnhancementFlags);
+        }
+        let _ = execute!(out, ResetColor, Show, LeaveAlternateScreen);
+        let _ = terminal::disable_raw_mode();
+    }
+}
+
+// Promedia el bloque del framebuffer que cae dentro de un píxel de la terminal.
+fn sample_area(framebuffer: &Framebuffer, x: usize, y: usize, columns: usize, rows: usize) -> u32 {
+    let x0 = x * framebuffer.width / columns;
+    let x1 = ((x + 1) * framebuffer.width / columns).max(x0 + 1);
+    let y0 = y * framebuffer.height / rows;
+    let y1 = ((y + 1) * framebuffer.height / rows).max(y0 + 1);
+
+    let (mut r, mut g, mut b, mut count) = (0, 0, 0, 0);
+    for sy in y0..y1 {
+        for sx in x0..x1 {
+            let color = framebuffer.get_pixel_color(sx, sy);
+            r += (color >> 16) & 0xFF;
+            g += (color >> 8) & 0xFF;
+            b += color & 0xFF;
+            count += 1;
+        }
+    }
+
+    ((r / count) << 16) | ((g / count) << 8) | (b / count)
+}
+
+fn to_color(color: u32) -> Color {
+    Color::Rgb {
+        r: ((color >> 16) & 0xFF) as u8,
+        g: ((color >> 8) & 0xFF) as u8,
+        b: (color & 0xFF) as u8,
+    }
+}
+
+fn map_key(code: KeyCode) -> Option<Key> {
+    let key = match code {
+        KeyCode::Esc => Key::Escape,
+        KeyCode::Enter => Key::Enter,
+        KeyCode::Backspace => Key::Backspace,
+        KeyCode::Tab => Key::Tab,
+        KeyCode::Up => Key::Up,
+        KeyCode::Down => Key::Down,
+        KeyCode::Left => Key::Left,
+        KeyCode::Right => Key::Right,
+        KeyCode::PageUp => Key::PageUp,
+        KeyCode::PageDown => Key::PageDown,
+        KeyCode::Home => Key::Home,
+        KeyCode::End => Key::End,
+        KeyCode::Insert => Key::Insert,
+        KeyCode::Delete => Key::Delete,
+        KeyCode::F(n) => return map_function_key(n),
+        KeyCode::Modifier(modifier) => return map_modifier(modifier),
+        KeyCode::Char(c) => return map_char(c),
+        _ => return None,
+    };
+    Some(key)
+}
+
+fn map_function_key(n: u8) -> Option<Key> {
+    let key = match n {
+        1 => Key::F1,
+        2 => Key::F2,
+        3 => Key::F3,
+        4 => Key::F4,
+        5 => Key::F5,
+        6 => Key::F6,
+        7 => Key::F7,
+        8 => Key::F8,
+        9 => Key::F9,
+        10 => Key::F10,
+        11 => Key::F11,
+        12 => Key::F12,
+        _ => return None,
+    };
+    Some(key)
+}
+
+// Solo llegan si la terminal informa todas las teclas como secuencias de escape
+fn map_modifier(modifier: ModifierKeyCode) -> Option<Key> {
+    let key = match modifier {
+        ModifierKeyCode::LeftShift => Key::LeftShift,
+        ModifierKeyCode::RightShift => Key::RightShift,
+        ModifierKeyCode::LeftControl => Key::LeftCtrl,
+        ModifierKeyCode::RightControl => Key::RightCtrl,
+        ModifierKeyCode::LeftAlt => Key::LeftAlt,
+        ModifierKeyCode::RightAlt => Key::RightAlt,
+        _ => return None,
+    };
+    Some(key)
+}
+
+fn map_char(c: char) -> Option<Key> {
+    let key = match c.to_ascii_lowercase() {
+        ' ' => Key::Space,
+        '0' => Key::Key0,
+        '1' => Key::Key1,
+        '2' => Key::Key2,
+        '3' => Key::Key3,
+        '4' => Key::Key4,
+        '5' => Key::Key5,
+        '6' => Key::Key6,
+        '7' => Key::Key7,
+        '8' => Key::Key8,
+        '9' => Key::Key9,
+        'a' => Key::A,
+        'b' => Key::B,
+        'c' => Key::C,
+        'd' => Key::D,
+        'e' => Key::E,
+        'f' => Key::F,
+        'g' => Key::G,
+        'h' => Key::H,
+        'i' => Key::I,
+        'j' => Key::J,
+        'k' => Key::K,
+        'l' => Key::L,
+        'm' => Key::M,
+        'n' => Key::N,
+        'o' => Key::O,
+        'p' => Key::P,
+        'q' => Key::Q,
+        'r' => Key::R,
+        's' => Key::S,
+        't' => Key::T,
+        'u' => Key::U,
+        'v' => Key::V,
+        'w' => Key::W,
+        'x' => Key::X,
+        'y' => Key::Y,
+        'z' => Key::Z,
+        _ => return None,
+    };
+    Some(key)
+}
@@ -0,0 +1,234 @@
+use std::collections::HashMap;
+use std::fs;
+use std::io::{self, ErrorKind};
+use std::sync::Mutex;
+
+use once_cell::sync::Lazy;
+use rusttype::{point, Scale};
+
+use crate::bitmap_font::{bitmap_text_size, draw_bitmap_text, BitmapStyle, CHAR_HEIGHT};
+use crate::framebuffer::Framebuffer;
+
+pub const DEFAULT_FONT: &str = "src/assets/fonts/DejaVuSans.ttf";
+
+// Fuente de toda la interfaz; sin el archivo se usa la fuente incorporada de bitmap_font
+static FONT: Lazy<Mutex<Option<Font>>> = Lazy::new(|| match Font::load(DEFAULT_FONT) {
+    Ok(font) => Mutex::new(Some(font)),
+    Err(err) => {
+        eprintln!("No se pudo abrir la fuente {}: {}", DEFAULT_FONT, err);
+        Mutex::new(None)
+    }
+});
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Align {
+    Left,   // `x` es el borde izquierdo
+    Center, // `x` es el centro de cada línea
+    Right,  // `x` es el borde derecho
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct TextStyle {
+    pub size: f32, // Alto en píxeles
+    pub color: u32,
+    pub align: Align,
+    pub max_width: Option<usize>, // Con ancho máximo las líneas se parten entre palabras
+}
+
+impl Default for TextStyle {
+    fn default() -> Self {
+        TextStyle { size: 18.0, color: 0xFFFFFF, align: Align::Left, max_width: None }
+    }
+}
+
+// Rectángulo que ocupa un texto dibujado o medido
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct Bounds {
+    pub x: i32,
+    pub y: i32,
+    pub width: usize,
+    pub height: usize,
+}
+
+// Glifo ya rasterizado: cobertura de 0 a 1 por píxel, relativa al origen en la línea base
+struct Glyph {
+    coverage: Vec<f32>,
+    width: usize,
+    height: usize,
+    left: i32,
+    top: i32,
+    advance: f32,
+    id: rusttype::GlyphId,
+}
+
+pub struct Font {
+    font: rusttype::Font<'static>,
+    glyphs: HashMap<(char, u32), Glyph>, // Por carácter y tamaño (los bits del f32)
+}
+
+impl Font {
+    pub fn load(path: &str) -> io::Result<Font> {
+        let data = fs::read(path)?;
+        let font = rusttype::Font::try_from_vec(data).ok_or_else(|| io::Error::new(ErrorKind::InvalidData, "no es una fuente TrueType"))?;
+        Ok(Font { font, glyphs: HashMap::new() })
+    }
+
+    fn glyph(&mut self, c: char, size: f32) -> &Glyph {
+        let font = &self.font;
+        self.glyphs.entry((c, size.to_bits())).or_insert_with(|| {
+            let scaled = font.glyph(c).scaled(Scale::uniform(size));
+            let advance = scaled.h_metrics().advance_width;
+            let id = scaled.id();
+            let positioned = scaled.positioned(point(0.0, 0.0));
+
+            // Los espacios no tienen contorno, solo avance
+            let Some(bounds) = positioned.pixel_bounding_box() else {
+                return Glyph { coverage: Vec::new(), width: 0, height: 0, left: 0, top: 0, advance, id };
+            };
+            let (width, height) = (bounds.width() as usize, bounds.height() as usize);
+            let mut coverage = vec![0.0; width * height];
+            positioned.draw(|x, y, value| coverage[y as usize * width + x as usize] = value);
+            Glyph { coverage, width, height, left: bounds.min.x, top: bounds.min.y, advance, id }
+        })
+    }
+
+    fn line_height(&self, size: f32) -> f32 {
+        let metrics = self.font.v_metrics(Scale::uniform(size));
+        (metrics.ascent - metrics.descent + metrics.line_gap).ceil()
+    }
+
+    // Ancho de una línea con el espacio entre pares de letras que indica la fuente
+    fn width(&mut self, text: &str, size: f32) -> f32 {
+        let mut width = 0.0;
+        let mut previous = None;
+        for c in text.chars() {
+            let (advance, id) = {
+                let glyph = self.glyph(c, size);
+                (glyph.advance, glyph.id)
+            };
+            if let Some(previous) = previous {
+                width += self.font.pair_kerning(Scale::uniform(size), previous, id);
+            }
+            width += advance;
+            previous = Some(id);
+        }
+        width
+    }
+
+    // Parte el texto en líneas: en cada salto de línea y, con ancho máximo, entre palabras.
+    // Una palabra más ancha que el máximo se parte entre letras.
+    fn wrap(&mut self, text: &str, style: &TextStyle) -> Vec<(String, f32)> {
+        let mut lines = Vec::new();
+        let Some(max_width) = style.max_width.map(|width| width as f32) else {
+            return text.split('\n').map(|line| (line.to_string(), self.width(line, style.size))).collect();
+        };
+
+        for paragraph in text.split('\n') {
+            let mut line = String::new();
+            for word in paragraph.split(' ') {
+                let candidate = if line.is_empty() { word.to_string() } else { format!("{} {}", line, word) };
+                if self.width(&candidate, style.size) <= max_width {
+                    line = candidate;
+                    continue;
+                }
+                if !line.is_empty() {
+                    lines.push(std::mem::take(&mut line));
+                }
+                for c in word.chars() {
+                    line.push(c);
+                    if self.width(&line, style.size) > max_width && line.chars().count() > 1 {
+                        line.pop();
+                        lines.push(std::mem::replace(&mut line, c.to_string()));
+                    }
+                }
+            }
+            lines.push(line);
+        }
+
+        lines.into_iter().map(|line| {
+            let width = self.width(&line, style.size);
+            (line, width)
+        }).collect()
+    }
+
+    pub fn measure(&mut self, text: &str, x: i32, y: i32, style: &TextStyle) -> Bounds {
+        let lines = self.wrap(text, style);
+        let width = lines.iter().map(|(_, width)| *width).fold(0.0, f32::max).ceil() as usize;
+        let left = match style.align {
+            Align::Left => x,
+            Align::Center => x - width as i32 / 2,
+            Align::Right => x - width as i32,
+        };
+        Bounds { x: left, y, width, height: lines.len() * self.line_height(style.size) as usize }
+    }
+
+    // Dibuja el texto con `y` en el borde superior de la primera línea; devuelve lo que ocupa
+    pub fn draw(&mut self, framebuffer: &mut Framebuffer, text: &str, x: i32, y: i32, style: &TextStyle) -> Bounds {
+        let scale = Scale::uniform(style.size);
+        let ascent = self.font.v_metrics(scale).ascent;
+        let line_height = self.line_height(style.size);
+
+        for (index, (line, width)) in self.wrap(text, style).iter().enumerate() {
+            let mut pen = match style.align {
+                Align::Left => x as f32,
+                Align::Center => x as f32 - width / 2.0,
+                Align::Right => x as f32 - width,
+            };
+            let baseline = (y as f32 + ascent + index as f32 * line_height).round() as i32;
+
+            let mut previous = None;
+            for c in line.chars() {
+                let kerning = previous.map_or(0.0, |previous| self.font.pair_kerning(scale, previous, self.font.glyph(c).id()));
+                pen += kerning;
+
+                let glyph = self.glyph(c, style.size);
+                let (left, top) = (pen.round() as i32 + glyph.left, baseline + glyph.top);
+                for gy in 0..glyph.height {
+                    for gx in 0..glyph.width {
+                        framebuffer.blend_pixel(left + gx as i32, top + gy as i32, style.color, glyph.coverage[gy * glyph.width + gx]);
+                    }
+                }
+                pen += glyph.advance;
+                previous = Some(glyph.id);
+            }
+        }
+
+        self.measure(text, x, y, style)
+    }
+}
+
+// Texto con la fuente de la interfaz
+pub fn draw_string(framebuffer: &mut Framebuffer, text: &str, x: i32, y: i32, style: &TextStyle) -> Bounds {
+    match FONT.lock().unwrap().as_mut() {
+        Some(font) => font.draw(framebuffer, text, x, y, style),
+        None => {
+            let bounds = fallback_bounds(text, x, y, style);
+            let bitmap_style = BitmapStyle { color: style.color, scale: fallback_scale(style), ..BitmapStyle::default() };
+            draw_bitmap_text(framebuffer, text, bounds.x, bounds.y, &bitmap_style);
+            bounds
+        }
+    }
+}
+
+// Lo que ocuparía el texto, sin dibujarlo
+pub fn measure_string(text: &str, x: i32, y: i32, style: &TextStyle) -> Bounds {
+    match FONT.lock().unwrap().as_mut() {
+        Some(font) => font.measure(text, x, y, style),
+        None => fallback_bounds(text, x, y, style),
+    }
+}
+
+// La fuente de mapa de bits solo escala por enteros y no parte líneas
+fn fallback_scale(style: &TextStyle) -> usize {
+    (style.size / CHAR_HEIGHT as f32).round().max(1.0) as usize
+}
+
+fn fallback_bounds(text: &str, x: i32, y: i32, style: &TextStyle) -> Bounds {
+    let (width, height) = bitmap_text_size(text, fallback_scale(style));
+    let left = match style.align {
+        Align::Left => x,
+        Align::Center => x - width as i32 / 2,
+        Align::Right => x - width as i32,
+    };
+    Bounds { x: left, y, width, height }
+}
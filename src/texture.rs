@@ -1,7 +1,13 @@
 extern crate image;
 
-use image::{ImageReader, Pixel};
-use image::{DynamicImage, GenericImageView};
+use image::Pixel;
+use image::{DynamicImage, GenericImageView, ImageResult};
+use once_cell::sync::Lazy;
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+
+// Texturas ya cargadas, para no leer el archivo de nuevo en cada cuadro
+static CACHE: Lazy<Mutex<HashMap<String, Arc<Texture>>>> = Lazy::new(|| Mutex::new(HashMap::new()));
 
 pub struct Texture {
   image: DynamicImage,
@@ -10,15 +16,35 @@ pub struct Texture {
 }
 
 impl Texture {
-  pub fn new(file_path: &str) -> Texture {
-    let img = ImageReader::open(file_path).unwrap().decode().unwrap();
+  pub fn new(file_path: &str) -> ImageResult<Texture> {
+    let img = image::open(file_path)?;
     let width = img.width();
     let height = img.height();
-    Texture { image: img, width, height }
+    Ok(Texture { image: img, width, height })
+  }
+
+  // Carga la textura una sola vez; los errores no se guardan, así se puede reintentar
+  pub fn load(file_path: &str) -> ImageResult<Arc<Texture>> {
+    if let Some(texture) = CACHE.lock().unwrap().get(file_path) {
+      return Ok(Arc::clone(texture));
+    }
+    let texture = Arc::new(Texture::new(file_path)?);
+    CACHE.lock().unwrap().insert(file_path.to_string(), Arc::clone(&texture));
+    Ok(texture)
+  }
+
+  // Para dibujar: si la textura no se puede abrir se usa `fallback`, avisando una sola vez
+  pub fn cached(file_path: &str, fallback: &str) -> Arc<Texture> {
+    Texture::load(file_path).unwrap_or_else(|err| {
+      eprintln!("No se pudo abrir la textura {}, se usa {}: {}", file_path, fallback, err);
+      let texture = Texture::load(fallback).expect("las texturas por defecto vienen con el juego");
+      CACHE.lock().unwrap().insert(file_path.to_string(), Arc::clone(&texture));
+      texture
+    })
   }
 
   pub fn get_pixel_color(&self, x: u32, y: u32) -> u32 {
-    let pixel = self.image.get_pixel(x, y).to_rgb();
+    let pixel = self.image.get_pixel(x.min(self.width - 1), y.min(self.height - 1)).to_rgb();
     let r = pixel[0];
     let g = pixel[1];
     let b = pixel[2];
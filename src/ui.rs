@@ -0,0 +1,351 @@
+use std::time::{Duration, Instant};
+
+use minifb::Key;
+
+use crate::backend::Backend;
+use crate::framebuffer::Framebuffer;
+use crate::input::{KeyEdges, BINDABLE_KEYS};
+use crate::text::{draw_string, Align, TextStyle};
+
+pub const ROW_HEIGHT: usize = 40;
+pub const PANEL_COLOR: u32 = 0x1A1A1A;
+pub const FOCUS_COLOR: u32 = 0x8ACE00;
+pub const TEXT_COLOR: u32 = 0xFFFFFF;
+const HOVER_COLOR: u32 = 0x3A3A3A;
+const TRACK_COLOR: u32 = 0x555555;
+const LABEL_SIZE: f32 = 20.0;
+const PADDING: usize = 10; // Del borde de una fila a su texto
+
+const UP: [Key; 2] = [Key::Up, Key::W];
+const DOWN: [Key; 2] = [Key::Down, Key::S];
+const LEFT: [Key; 2] = [Key::Left, Key::A];
+const RIGHT: [Key; 2] = [Key::Right, Key::D];
+const ACTIVATE: [Key; 2] = [Key::Enter, Key::Space];
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Rect {
+    pub x: usize,
+    pub y: usize,
+    pub width: usize,
+    pub height: usize,
+}
+
+impl Rect {
+    pub fn new(x: usize, y: usize, width: usize, height: usize) -> Self {
+        Rect { x, y, width, height }
+    }
+
+    pub fn contains(&self, (x, y): (f32, f32)) -> bool {
+        x >= self.x as f32 && y >= self.y as f32 && x < (self.x + self.width) as f32 && y < (self.y + self.height) as f32
+    }
+
+    // Donde empieza la columna del valor en las filas con etiqueta y valor
+    fn value_x(&self) -> usize {
+        self.x + self.width / 2
+    }
+}
+
+// Interfaz en modo inmediato: en cada cuadro la pantalla llama a `begin`, declara
+// sus controles en orden, que se dibujan y devuelven lo que hizo el jugador, y
+// termina con `end`. Entre cuadros solo se recuerda qué control tiene el foco (por
+// su posición en ese orden) y cuál se está arrastrando con el mouse.
+pub struct Ui {
+    pub accent: u32, // Color de la fila con el foco
+    keys: KeyEdges,
+    pressed: Vec<Key>, // Teclas presionadas en este cuadro que ningún control usó todavía
+    mouse: Option<(f32, f32)>,
+    mouse_moved: bool,
+    mouse_down: bool,
+    clicked: bool,
+    focus: usize,
+    count: usize, // Controles declarados en este cuadro
+    dragging: Option<usize>,
+    last_input: Instant,
+}
+
+impl Default for Ui {
+    fn default() -> Self {
+        Ui {
+            accent: FOCUS_COLOR,
+            keys: KeyEdges::default(),
+            pressed: Vec::new(),
+            mouse: None,
+            mouse_moved: false,
+            mouse_down: true,
+            clicked: false,
+            focus: 0,
+            count: 0,
+            dragging: None,
+            last_input: Instant::now(),
+        }
+    }
+}
+
+impl Ui {
+    // Lo que ya está presionado al abrir la pantalla, tecla o botón, no cuenta hasta soltarse
+    pub fn reset(&mut self) {
+        self.keys.block();
+        self.mouse_down = true;
+        self.dragging = None;
+        self.last_input = Instant::now();
+    }
+
+    pub fn begin(&mut self, window: &dyn Backend) {
+        self.pressed = BINDABLE_KEYS.into_iter().filter(|key| self.keys.pressed(window, *key)).collect();
+
+        // Solo un movimiento real mueve el foco, no un cursor quieto sobre otra fila
+        let mouse = window.get_mouse_pos();
+        self.mouse_moved = matches!((self.mouse, mouse), (Some(last), Some(current)) if last != current);
+        self.mouse = mouse;
+
+        let down = window.is_mouse_down();
+        self.clicked = down && !self.mouse_down;
+        self.mouse_down = down;
+        if !down {
+            self.dragging = None;
+        }
+
+        if !self.pressed.is_empty() || self.mouse_moved || self.clicked {
+            self.last_input = Instant::now();
+        }
+        self.count = 0;
+    }
+
+    // Arriba y abajo que ningún control usó mueven el foco
+    pub fn end(&mut self) {
+        if self.count == 0 {
+            return;
+        }
+        if self.take(&UP) {
+            self.focus = self.focus.checked_sub(1).unwrap_or(self.count - 1);
+        }
+        if self.take(&DOWN) {
+            self.focus = (self.focus + 1) % self.count;
+        }
+        self.focus = self.focus.min(self.count - 1);
+    }
+
+    // Tiempo desde la última tecla o movimiento del mouse
+    pub fn idle(&self) -> Duration {
+        self.last_input.elapsed()
+    }
+
+    // Tecla presionada en este cuadro, para atajos propios de cada pantalla
+    pub fn pressed(&self, key: Key) -> bool {
+        self.pressed.contains(&key)
+    }
+
+    // Devuelve y consume todas las teclas presionadas en este cuadro
+    pub fn take_pressed(&mut self) -> Vec<Key> {
+        std::mem::take(&mut self.pressed)
+    }
+
+    pub fn focus(&self) -> usize {
+        self.focus
+    }
+
+    fn take(&mut self, keys: &[Key]) -> bool {
+        let before = self.pressed.len();
+        self.pressed.retain(|key| !keys.contains(key));
+        self.pressed.len() != before
+    }
+
+    // Registra un control; pasar el mouse por encima le da el foco
+    fn focusable(&mut self, rect: Rect) -> (usize, bool, bool) {
+        let id = self.count;
+        self.count += 1;
+        let hovered = self.mouse.is_some_and(|mouse| rect.contains(mouse));
+        if hovered && (self.mouse_moved || self.clicked) {
+            self.focus = id;
+        }
+        (id, self.focus == id, hovered)
+    }
+
+    pub fn panel(&self, framebuffer: &mut Framebuffer, rect: Rect) {
+        fill_rect(framebuffer, rect, PANEL_COLOR);
+    }
+
+    // `y` es el borde superior de la fila
+    pub fn label(&self, framebuffer: &mut Framebuffer, text: &str, x: usize, y: usize, color: u32) {
+        let style = TextStyle { size: LABEL_SIZE, color, ..TextStyle::default() };
+        draw_string(framebuffer, text, x as i32, y as i32 + 4, &style);
+    }
+
+    fn row(&self, framebuffer: &mut Framebuffer, rect: Rect, focused: bool, hovered: bool) {
+        if focused {
+            fill_rect(framebuffer, rect, self.accent);
+        } else if hovered {
+            fill_rect(framebuffer, rect, HOVER_COLOR);
+        }
+    }
+
+    // Devuelve true cuando se elige con Enter, Espacio o un clic
+    pub fn button(&mut self, framebuffer: &mut Framebuffer, rect: Rect, text: &str) -> bool {
+        let (_, focused, hovered) = self.focusable(rect);
+        self.row(framebuffer, rect, focused, hovered);
+        self.label(framebuffer, text, rect.x + PADDING, rect.y, TEXT_COLOR);
+        (focused && self.take(&ACTIVATE)) || (hovered && self.clicked)
+    }
+
+    // Botón con un valor a la derecha, como las teclas de cada acción
+    pub fn field(&mut self, framebuffer: &mut Framebuffer, rect: Rect, text: &str, value: &str) -> bool {
+        let chosen = self.button(framebuffer, rect, text);
+        self.label(framebuffer, value, rect.value_x(), rect.y, TEXT_COLOR);
+        chosen
+    }
+
+    // Devuelve true si el valor cambió
+    pub fn toggle(&mut self, framebuffer: &mut Framebuffer, rect: Rect, text: &str, value: &mut bool) -> bool {
+        let (_, focused, hovered) = self.focusable(rect);
+        let changed = (focused && (self.take(&ACTIVATE) | self.take(&LEFT) | self.take(&RIGHT))) || (hovered && self.clicked);
+        if changed {
+            *value = !*value;
+        }
+
+        self.row(framebuffer, rect, focused, hovered);
+        self.label(framebuffer, text, rect.x + PADDING, rect.y, TEXT_COLOR);
+
+        let size = 18;
+        let square = Rect::new(rect.value_x(), rect.y + (rect.height - size) / 2, size, size);
+        if *value {
+            fill_rect(framebuffer, square, TEXT_COLOR);
+        } else {
+            fill_rect(framebuffer, square, PANEL_COLOR);
+            framebuffer.rect(square.x as i32, square.y as i32, size, size, TEXT_COLOR);
+            framebuffer.rect(square.x as i32 + 1, square.y as i32 + 1, size - 2, size - 2, TEXT_COLOR);
+        }
+        self.label(framebuffer, if *value { "Sí" } else { "No" }, square.x + size + PADDING, rect.y, TEXT_COLOR);
+        changed
+    }
+
+    // Valor entre `min` y `max` en pasos de `step`: izquierda y derecha lo cambian y
+    // el mouse lo arrastra. `format` da el texto que se muestra. Devuelve true si cambió.
+    #[allow(clippy::too_many_arguments)]
+    pub fn slider(
+        &mut self,
+        framebuffer: &mut Framebuffer,
+        rect: Rect,
+        text: &str,
+        value: &mut f32,
+        (min, max): (f32, f32),
+        step: f32,
+        format: impl Fn(f32) -> String,
+    ) -> bool {
+        let (id, focused, hovered) = self.focusable(rect);
+        let track = Rect::new(rect.value_x(), rect.y + rect.height / 2 - 3, rect.width / 4, 6);
+
+        // Redondear a múltiplos del paso evita que se acumulen errores como 0.30000001
+        let snap = |value: f32| ((value / step).round() * step).clamp(min, max);
+        let mut target = *value;
+        if focused && self.take(&LEFT) {
+            target = snap(target - step);
+        }
+        if focused && (self.take(&RIGHT) | self.take(&ACTIVATE)) {
+            target = snap(target + step);
+        }
+        if hovered && self.clicked {
+            self.dragging = Some(id);
+        }
+        if let (Some(dragging), Some((mouse_x, _))) = (self.dragging, self.mouse) {
+            if dragging == id {
+                let fraction = ((mouse_x - track.x as f32) / track.width as f32).clamp(0.0, 1.0);
+                target = snap(min + fraction * (max - min));
+            }
+        }
+        let changed = target != *value;
+        *value = target;
+
+        self.row(framebuffer, rect, focused, hovered);
+        self.label(framebuffer, text, rect.x + PADDING, rect.y, TEXT_COLOR);
+
+        let fraction = if max > min { (*value - min) / (max - min) } else { 0.0 };
+        let filled = (fraction * track.width as f32).round() as usize;
+        fill_rect(framebuffer, track, TRACK_COLOR);
+        fill_rect(framebuffer, Rect::new(track.x, track.y, filled, track.height), TEXT_COLOR);
+        let knob = Rect::new((track.x + filled).saturating_sub(3), track.y.saturating_sub(6), 6, track.height + 12);
+        fill_rect(framebuffer, knob, TEXT_COLOR);
+
+        self.label(framebuffer, &format(*value), track.x + track.width + 2 * PADDING, rect.y, TEXT_COLOR);
+        changed
+    }
+
+    // Lista con páginas de `rows` filas. Arriba y abajo mueven `selected`, izquierda
+    // y derecha (o Re Pág y Av Pág) cambian de página y el mouse elige la fila bajo
+    // el cursor. Devuelve la fila elegida con Enter, Espacio o un clic.
+    pub fn list(&mut self, framebuffer: &mut Framebuffer, rect: Rect, items: &[(String, u32)], selected: &mut usize, rows: usize) -> Option<usize> {
+        if items.is_empty() {
+            return None;
+        }
+        let (_, focused, _) = self.focusable(rect);
+        let rows = rows.max(1);
+        let last = items.len() - 1;
+        let mut chosen = None;
+
+        if focused {
+            if self.take(&UP) {
+                *selected = selected.checked_sub(1).unwrap_or(last);
+            }
+            if self.take(&DOWN) {
+                *selected = if *selected >= last { 0 } else { *selected + 1 };
+            }
+            if self.take(&LEFT) | self.take(&[Key::PageUp]) {
+                *selected = selected.saturating_sub(rows);
+            }
+            if self.take(&RIGHT) | self.take(&[Key::PageDown]) {
+                *selected = (*selected + rows).min(last);
+            }
+            if self.take(&ACTIVATE) {
+                chosen = Some(*selected);
+            }
+        }
+        *selected = (*selected).min(last);
+
+        let page_count = items.len().div_ceil(rows);
+        let row = |offset: usize| Rect::new(rect.x, rect.y + offset * ROW_HEIGHT, rect.width, ROW_HEIGHT - 8);
+        let footer = row(rows);
+
+        // Flechas para cambiar de página con el mouse
+        let previous = Rect::new(footer.x, footer.y, 40, footer.height);
+        let next = Rect::new(footer.x + footer.width - 40, footer.y, 40, footer.height);
+        let page = *selected / rows;
+        if self.clicked && self.mouse.is_some_and(|mouse| previous.contains(mouse)) && page > 0 {
+            *selected = (page - 1) * rows;
+        }
+        if self.clicked && self.mouse.is_some_and(|mouse| next.contains(mouse)) && page + 1 < page_count {
+            *selected = (page + 1) * rows;
+        }
+
+        let page = *selected / rows;
+        let first = page * rows;
+        for (offset, (text, color)) in items[first..].iter().take(rows).enumerate() {
+            let area = row(offset);
+            let index = first + offset;
+            let hovered = self.mouse.is_some_and(|mouse| area.contains(mouse));
+            if hovered && (self.mouse_moved || self.clicked) {
+                *selected = index;
+            }
+            if hovered && self.clicked {
+                chosen = Some(index);
+            }
+            if index == *selected {
+                fill_rect(framebuffer, area, if focused { self.accent } else { HOVER_COLOR });
+            }
+            self.label(framebuffer, text, area.x + PADDING, area.y, *color);
+        }
+
+        let footer_style = TextStyle { size: LABEL_SIZE, align: Align::Center, ..TextStyle::default() };
+        let center = (footer.x + footer.width / 2) as i32;
+        draw_string(framebuffer, &format!("Página {}/{}", page + 1, page_count), center, footer.y as i32 + 4, &footer_style);
+        if page > 0 {
+            self.label(framebuffer, "<", previous.x + PADDING, previous.y, TEXT_COLOR);
+        }
+        if page + 1 < page_count {
+            self.label(framebuffer, ">", next.x + next.width - 2 * PADDING, next.y, TEXT_COLOR);
+        }
+        chosen
+    }
+}
+
+fn fill_rect(framebuffer: &mut Framebuffer, rect: Rect, color: u32) {
+    framebuffer.fill_rect(rect.x as i32, rect.y as i32, rect.width, rect.height, color);
+}
@@ -0,0 +1,32 @@
+use raycasting::bitmap_font::glyph;
+
+#[test]
+fn printable_ascii_is_covered() {
+    for c in ' '..='~' {
+        assert!(glyph(c).is_some(), "falta {:?}", c);
+    }
+}
+
+#[test]
+fn latin1_letters_are_covered() {
+    for c in 'À'..='ÿ' {
+        assert!(glyph(c).is_some(), "falta {:?}", c);
+    }
+    for c in "¡¿°«»·ÆØßÐÞæøðþ".chars() {
+        assert!(glyph(c).is_some(), "falta {:?}", c);
+    }
+}
+
+#[test]
+fn accents_change_the_base_letter() {
+    for (accented, base) in [('á', 'a'), ('É', 'E'), ('ñ', 'n'), ('Ü', 'U'), ('ç', 'c')] {
+        assert_ne!(glyph(accented), glyph(base), "{:?}", accented);
+    }
+}
+
+#[test]
+fn unknown_characters_have_no_glyph() {
+    for c in ['\t', '€', 'Ω', '→'] {
+        assert!(glyph(c).is_none(), "{:?}", c);
+    }
+}
@@ -0,0 +1,31 @@
+use raycasting::bot::Demo;
+use raycasting::generator::{generate, Algorithm};
+use raycasting::levels::{discover_levels, LEVELS_DIR};
+use raycasting::maze::Maze;
+
+fn play(maze: Maze) -> bool {
+    let mut demo = Demo::new(maze).expect("la meta debe ser alcanzable");
+    while demo.step() {}
+    demo.is_won()
+}
+
+#[test]
+fn bot_completes_every_bundled_level() {
+    let levels = discover_levels(LEVELS_DIR);
+    assert!(!levels.is_empty());
+
+    for level in levels {
+        let maze = level.maze.unwrap_or_else(|err| panic!("{}: {}", level.path, err));
+        assert!(play(maze), "el bot no pudo terminar {}", level.path);
+    }
+}
+
+#[test]
+fn bot_completes_generated_mazes() {
+    for algorithm in Algorithm::ALL {
+        for seed in 0..10 {
+            let maze = generate(algorithm, 10, 8, seed).to_maze();
+            assert!(play(maze), "el bot no pudo terminar {} con semilla {}", algorithm.name(), seed);
+        }
+    }
+}
@@ -0,0 +1,78 @@
+use raycasting::cli::{parse, BackendKind, Command, PlayOptions};
+use raycasting::generator::Algorithm;
+
+fn run(args: &[&str]) -> Result<Command, String> {
+    parse(&args.iter().map(|arg| arg.to_string()).collect::<Vec<_>>())
+}
+
+fn play(args: &[&str]) -> PlayOptions {
+    match run(args) {
+        Ok(Command::Play(options)) => options,
+        other => panic!("{:?} dio {:?}", args, other),
+    }
+}
+
+fn error(args: &[&str]) -> String {
+    match run(args) {
+        Err(err) => err,
+        Ok(command) => panic!("{:?} debía fallar y dio {:?}", args, command),
+    }
+}
+
+#[test]
+fn no_arguments_start_in_the_menu() {
+    let options = play(&[]);
+    assert_eq!(options.level, None);
+    assert_eq!(options.backend, BackendKind::Window);
+    assert!(!options.no_audio);
+}
+
+#[test]
+fn help_and_validate() {
+    assert_eq!(run(&["--help"]), Ok(Command::Help));
+    assert_eq!(run(&["-h", "--level", "x"]), Ok(Command::Help));
+    assert_eq!(run(&["validate", "a.txt", "--seed"]), Ok(Command::Validate(vec!["a.txt".to_string(), "--seed".to_string()])));
+}
+
+#[test]
+fn options_are_read() {
+    let options = play(&["mazes/maze1.txt", "--terminal", "--resolution", "1600x900", "--no-audio", "--record", "partida.rec"]);
+    assert_eq!(options.level.as_deref(), Some("mazes/maze1.txt"));
+    assert_eq!(options.backend, BackendKind::Terminal);
+    assert_eq!(options.resolution, Some((1600, 900)));
+    assert!(options.no_audio);
+    assert_eq!(options.record.as_deref(), Some("partida.rec"));
+
+    let options = play(&["--headless", "--replay", "partida.rec"]);
+    assert_eq!(options.backend, BackendKind::Headless);
+    assert_eq!(options.replay.as_deref(), Some("partida.rec"));
+}
+
+#[test]
+fn seed_becomes_an_algorithm_and_seed_level() {
+    assert_eq!(play(&["--seed", "42", "--algorithm", "kruskal"]).level.as_deref(), Some("kruskal:42"));
+
+    // Sin algoritmo se elige con la semilla
+    let expected = Algorithm::ALL[(7 % Algorithm::ALL.len() as u64) as usize];
+    assert_eq!(play(&["--seed", "7"]).level, Some(format!("{}:7", expected.name())));
+}
+
+#[test]
+fn invalid_combinations_are_rejected() {
+    assert!(error(&["--replay", "partida.rec", "--level", "mazes/maze1.txt"]).contains("grabación"));
+    assert!(error(&["--replay", "partida.rec", "--record", "otra.rec"]).contains("grabar"));
+    assert!(error(&["--headless"]).contains("--replay"));
+    assert!(error(&["--algorithm", "prim"]).contains("--seed"));
+    assert!(error(&["mazes/maze1.txt", "--level", "mazes/maze2.txt"]).contains("un nivel"));
+    assert!(error(&["mazes/maze1.txt", "--seed", "3"]).contains("un nivel"));
+}
+
+#[test]
+fn invalid_values_are_rejected() {
+    assert!(error(&["--seed"]).contains("--seed"));
+    assert!(error(&["--seed", "abc"]).contains("semilla"));
+    assert!(error(&["--seed", "1", "--algorithm", "dfs"]).contains("algoritmo"));
+    assert!(error(&["--backend", "opengl"]).contains("backend"));
+    assert!(error(&["--resolution", "10x10"]).contains("resolución"));
+    assert!(error(&["--fullscreen"]).contains("--fullscreen"));
+}
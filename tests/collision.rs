@@ -0,0 +1,85 @@
+use nalgebra_glm::Vec2;
+use raycasting::generator::Rng;
+use raycasting::maze::Maze;
+
+// Celdas de 100 unidades: el interior libre va de x = 100 a 600 y de y = 100 a 400,
+// con un pilar en x = 300..400, y = 200..300
+const ROOM: &str = "+-----+\n|S    |\n|  +  |\n|    g|\n+-----+\n";
+const RADIUS: f32 = 20.0;
+
+fn room() -> Maze {
+    Maze::parse(ROOM).unwrap()
+}
+
+#[test]
+fn free_move_is_unchanged() {
+    let pos = room().slide_circle(Vec2::new(150.0, 150.0), Vec2::new(10.0, 5.0), RADIUS);
+    assert_eq!(pos, Vec2::new(160.0, 155.0));
+}
+
+#[test]
+fn stops_flush_against_wall() {
+    let pos = room().slide_circle(Vec2::new(130.0, 150.0), Vec2::new(-30.0, 0.0), RADIUS);
+    assert!((pos.x - 120.0).abs() < 0.5, "x = {}", pos.x);
+    assert_eq!(pos.y, 150.0);
+}
+
+#[test]
+fn slides_along_wall_when_moving_diagonally() {
+    let pos = room().slide_circle(Vec2::new(125.0, 150.0), Vec2::new(-20.0, 15.0), RADIUS);
+    assert!((pos.x - 120.0).abs() < 0.5, "x = {}", pos.x);
+    assert_eq!(pos.y, 165.0);
+}
+
+#[test]
+fn cannot_cut_through_corner_diagonally() {
+    let maze = room();
+    // Justo afuera de la esquina superior izquierda del pilar (300, 200)
+    let from = Vec2::new(285.0, 185.0);
+    assert!(!maze.circle_hits_wall(from, RADIUS));
+
+    let pos = maze.slide_circle(from, Vec2::new(10.0, 10.0), RADIUS);
+    assert!(!maze.circle_hits_wall(pos, RADIUS));
+    let corner = Vec2::new(300.0, 200.0);
+    assert!((pos - corner).magnitude() >= RADIUS - 0.1, "pos = {:?}", pos);
+}
+
+#[test]
+fn slides_around_corner_along_free_axis() {
+    let maze = room();
+    // Arriba del pilar, empujando hacia abajo y a la derecha: baja bloqueado, avanza en x
+    let pos = maze.slide_circle(Vec2::new(350.0, 175.0), Vec2::new(10.0, 10.0), RADIUS);
+    assert_eq!(pos.x, 360.0);
+    assert!((pos.y - 180.0).abs() < 0.5, "y = {}", pos.y);
+}
+
+#[test]
+fn radius_is_configurable() {
+    let maze = room();
+    let center = Vec2::new(150.0, 150.0);
+    assert!(!maze.circle_hits_wall(center, 40.0));
+    assert!(maze.circle_hits_wall(center, 55.0));
+
+    // Con un radio mayor el jugador queda más lejos de la pared
+    let pos = maze.slide_circle(Vec2::new(160.0, 150.0), Vec2::new(-50.0, 0.0), 45.0);
+    assert!((pos.x - 145.0).abs() < 0.5, "x = {}", pos.x);
+}
+
+#[test]
+fn outside_the_maze_counts_as_wall() {
+    let maze = room();
+    assert!(maze.circle_hits_wall(Vec2::new(-10.0, 150.0), RADIUS));
+    assert!(maze.circle_hits_wall(Vec2::new(800.0, 150.0), RADIUS));
+}
+
+#[test]
+fn random_moves_never_end_inside_a_wall() {
+    let maze = room();
+    let mut rng = Rng::new(7);
+    let mut pos = Vec2::new(150.0, 150.0);
+    for _ in 0..10_000 {
+        let delta = Vec2::new(rng.below(21) as f32 - 10.0, rng.below(21) as f32 - 10.0);
+        pos = maze.slide_circle(pos, delta, RADIUS);
+        assert!(!maze.circle_hits_wall(pos, RADIUS), "pos = {:?}", pos);
+    }
+}
@@ -0,0 +1,71 @@
+use raycasting::daily::{record_time, times_for, Date, DAILY_RECORDS_FILE};
+
+fn date(year: i32, month: u32, day: u32) -> Date {
+    Date { year, month, day }
+}
+
+#[test]
+fn days_map_to_calendar_dates() {
+    assert_eq!(Date::from_days(0), date(1970, 1, 1));
+    assert_eq!(Date::from_days(-1), date(1969, 12, 31));
+
+    // Cambio de año
+    assert_eq!(Date::from_days(19_722), date(2023, 12, 31));
+    assert_eq!(Date::from_days(19_723), date(2024, 1, 1));
+
+    // 2000 y 2024 son bisiestos, 2100 no
+    assert_eq!(Date::from_days(11_016), date(2000, 2, 29));
+    assert_eq!(Date::from_days(11_017), date(2000, 3, 1));
+    assert_eq!(Date::from_days(19_782), date(2024, 2, 29));
+    assert_eq!(Date::from_days(47_540), date(2100, 2, 28));
+    assert_eq!(Date::from_days(47_541), date(2100, 3, 1));
+}
+
+#[test]
+fn dates_round_trip_through_text() {
+    for days in [-1, 0, 11_016, 19_722, 19_723, 47_541] {
+        let date = Date::from_days(days);
+        assert_eq!(Date::parse(&date.to_string()), Some(date));
+    }
+    assert_eq!(date(2024, 3, 5).to_string(), "2024-03-05");
+}
+
+#[test]
+fn invalid_dates_are_rejected() {
+    for text in ["2024-13-01", "2024-00-10", "2024-01-00", "2024-04-31", "2023-02-29", "2100-02-29", "2024-02", "hoy"] {
+        assert_eq!(Date::parse(text), None, "{}", text);
+    }
+    assert_eq!(Date::parse("2000-02-29"), Some(date(2000, 2, 29)));
+}
+
+#[test]
+fn the_same_day_gives_the_same_maze() {
+    let day = date(2024, 2, 29);
+    assert_eq!(day.seed(), date(2024, 2, 29).seed());
+    assert_eq!(day.algorithm(), date(2024, 2, 29).algorithm());
+    assert_ne!(day.seed(), date(2024, 3, 1).seed());
+}
+
+#[test]
+fn times_are_sorted_and_damaged_lines_skipped() {
+    // Carpeta de configuración propia para no tocar la del usuario
+    let home = std::env::temp_dir().join("daily_records_test");
+    let _ = std::fs::remove_dir_all(&home);
+    std::env::set_var("XDG_CONFIG_HOME", &home);
+
+    let day = date(2024, 2, 29);
+    record_time(day, 42.5).unwrap();
+    record_time(date(2024, 3, 1), 10.0).unwrap();
+    record_time(day, 30.25).unwrap();
+
+    let path = home.join("brat_maze").join(DAILY_RECORDS_FILE);
+    let mut text = std::fs::read_to_string(&path).unwrap();
+    text.push_str("2024-02-30\t5.00\tnadie\n");
+    text.push_str("2024-02-29\trápido\tnadie\n");
+    text.push_str("basura\n");
+    text.push_str("2024-02-29\t35.00\n");
+    std::fs::write(&path, text).unwrap();
+
+    let seconds: Vec<f32> = times_for(day).iter().map(|record| record.seconds).collect();
+    assert_eq!(seconds, [30.25, 35.0, 42.5]);
+}
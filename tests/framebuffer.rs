@@ -0,0 +1,240 @@
+use image::{Rgba, RgbaImage};
+use raycasting::framebuffer::{Filter, Framebuffer};
+use raycasting::render::draw_image;
+
+const WHITE: u32 = 0xFFFFFF;
+
+// Coordenadas de los píxeles pintados, en orden de filas
+fn painted(framebuffer: &Framebuffer) -> Vec<(i32, i32)> {
+    framebuffer.buffer.iter().enumerate()
+        .filter(|(_, &pixel)| pixel != 0)
+        .map(|(index, _)| ((index % framebuffer.width) as i32, (index / framebuffer.width) as i32))
+        .collect()
+}
+
+fn pixel(framebuffer: &Framebuffer, x: usize, y: usize) -> u32 {
+    framebuffer.get_pixel_color(x, y)
+}
+
+#[test]
+fn set_pixel_ignores_points_outside() {
+    let mut framebuffer = Framebuffer::new(4, 4);
+    framebuffer.set_pixel(-1, 0, WHITE);
+    framebuffer.set_pixel(0, 4, WHITE);
+    framebuffer.set_pixel(3, 3, WHITE);
+    assert_eq!(painted(&framebuffer), vec![(3, 3)]);
+}
+
+#[test]
+fn fill_rect_is_clipped_to_the_buffer() {
+    let mut framebuffer = Framebuffer::new(4, 4);
+    framebuffer.fill_rect(-2, 2, 4, 10, WHITE);
+    assert_eq!(painted(&framebuffer), vec![(0, 2), (1, 2), (0, 3), (1, 3)]);
+
+    // Del todo afuera no dibuja nada
+    let mut framebuffer = Framebuffer::new(4, 4);
+    framebuffer.fill_rect(5, 0, 3, 3, WHITE);
+    framebuffer.fill_rect(-3, 0, 3, 3, WHITE);
+    assert!(painted(&framebuffer).is_empty());
+}
+
+#[test]
+fn rect_draws_only_the_border() {
+    let mut framebuffer = Framebuffer::new(5, 5);
+    framebuffer.rect(0, 0, 4, 3, WHITE);
+    assert_eq!(painted(&framebuffer), vec![(0, 0), (1, 0), (2, 0), (3, 0), (0, 1), (3, 1), (0, 2), (1, 2), (2, 2), (3, 2)]);
+}
+
+#[test]
+fn line_covers_both_endpoints_in_every_direction() {
+    for (x0, y0, x1, y1) in [(0, 0, 4, 0), (4, 4, 4, 0), (0, 0, 4, 4), (4, 0, 0, 4), (0, 0, 4, 2), (3, 4, 1, 0)] {
+        let mut framebuffer = Framebuffer::new(5, 5);
+        framebuffer.line(x0, y0, x1, y1, WHITE);
+        let points = painted(&framebuffer);
+        assert!(points.contains(&(x0, y0)) && points.contains(&(x1, y1)), "{:?}", (x0, y0, x1, y1));
+
+        // Un píxel por columna o por fila, según el eje más largo, sin huecos
+        let length = (x1 - x0).abs().max((y1 - y0).abs()) + 1;
+        assert_eq!(points.len() as i32, length, "{:?}", (x0, y0, x1, y1));
+    }
+}
+
+#[test]
+fn line_matches_bresenham() {
+    // y = 0.4 x redondeado, sin empates
+    let mut framebuffer = Framebuffer::new(6, 3);
+    framebuffer.line(0, 0, 5, 2, WHITE);
+    assert_eq!(painted(&framebuffer), vec![(0, 0), (1, 0), (2, 1), (3, 1), (4, 2), (5, 2)]);
+}
+
+#[test]
+fn line_is_clipped_to_the_buffer() {
+    let mut framebuffer = Framebuffer::new(4, 4);
+    framebuffer.line(-10, 1, 10, 1, WHITE);
+    assert_eq!(painted(&framebuffer), vec![(0, 1), (1, 1), (2, 1), (3, 1)]);
+}
+
+#[test]
+fn fill_circle_is_a_symmetric_disc() {
+    let mut framebuffer = Framebuffer::new(5, 5);
+    framebuffer.fill_circle(2, 2, 2, WHITE);
+    let points = painted(&framebuffer);
+    assert_eq!(points.len(), 13);
+    for (x, y) in points {
+        assert!((x - 2).pow(2) + (y - 2).pow(2) <= 4);
+    }
+}
+
+#[test]
+fn circle_draws_the_outline() {
+    let mut framebuffer = Framebuffer::new(9, 9);
+    framebuffer.circle(4, 4, 3, WHITE);
+    let points = painted(&framebuffer);
+    assert!(!points.contains(&(4, 4)));
+    for &(x, y) in &points {
+        let distance = (((x - 4).pow(2) + (y - 4).pow(2)) as f32).sqrt();
+        assert!((distance - 3.0).abs() < 1.0, "({}, {}) a {}", x, y, distance);
+        assert!(points.contains(&(8 - x, y)) && points.contains(&(x, 8 - y)) && points.contains(&(y, x)));
+    }
+    for extreme in [(1, 4), (7, 4), (4, 1), (4, 7)] {
+        assert!(points.contains(&extreme));
+    }
+}
+
+#[test]
+fn blend_pixel_mixes_with_the_background() {
+    let mut framebuffer = Framebuffer::new(1, 1);
+    framebuffer.buffer[0] = 0x0000FF;
+    framebuffer.blend_pixel(0, 0, 0xFF0000, 0.5);
+    assert_eq!(pixel(&framebuffer, 0, 0), 0x800080);
+    framebuffer.blend_pixel(0, 0, WHITE, 0.0);
+    assert_eq!(pixel(&framebuffer, 0, 0), 0x800080);
+    framebuffer.blend_pixel(0, 0, WHITE, 1.0);
+    assert_eq!(pixel(&framebuffer, 0, 0), WHITE);
+}
+
+#[test]
+fn copy_from_is_clipped_on_every_side() {
+    let mut source = Framebuffer::new(3, 3);
+    for (index, pixel) in source.buffer.iter_mut().enumerate() {
+        *pixel = index as u32 + 1;
+    }
+
+    let mut framebuffer = Framebuffer::new(3, 3);
+    framebuffer.copy_from(&source, -1, 2);
+    assert_eq!(framebuffer.buffer, vec![0, 0, 0, 0, 0, 0, 2, 3, 0]);
+
+    let mut framebuffer = Framebuffer::new(3, 3);
+    framebuffer.copy_from(&source, 2, -2);
+    assert_eq!(framebuffer.buffer, vec![0, 0, 7, 0, 0, 0, 0, 0, 0]);
+}
+
+fn checker() -> RgbaImage {
+    let mut image = RgbaImage::new(2, 2);
+    image.put_pixel(0, 0, Rgba([255, 0, 0, 255]));
+    image.put_pixel(1, 0, Rgba([0, 255, 0, 255]));
+    image.put_pixel(0, 1, Rgba([0, 0, 255, 255]));
+    image.put_pixel(1, 1, Rgba([255, 255, 255, 255]));
+    image
+}
+
+#[test]
+fn blit_nearest_scales_up_and_down() {
+    let mut framebuffer = Framebuffer::new(4, 4);
+    framebuffer.blit(&checker(), 0, 0, 4, 4, Filter::Nearest);
+    assert_eq!(pixel(&framebuffer, 1, 1), 0xFF0000);
+    assert_eq!(pixel(&framebuffer, 2, 1), 0x00FF00);
+    assert_eq!(pixel(&framebuffer, 1, 2), 0x0000FF);
+    assert_eq!(pixel(&framebuffer, 3, 3), WHITE);
+
+    // Escalas que no son enteras también cubren todo el destino
+    let mut framebuffer = Framebuffer::new(3, 3);
+    framebuffer.blit(&checker(), 0, 0, 3, 3, Filter::Nearest);
+    assert!(framebuffer.buffer.iter().all(|&pixel| pixel != 0));
+
+    let mut framebuffer = Framebuffer::new(1, 1);
+    framebuffer.blit(&checker(), 0, 0, 1, 1, Filter::Nearest);
+    assert_eq!(pixel(&framebuffer, 0, 0), WHITE);
+}
+
+#[test]
+fn blit_bilinear_interpolates_between_pixels() {
+    let mut image = RgbaImage::new(2, 1);
+    image.put_pixel(1, 0, Rgba([255, 255, 255, 255]));
+    image.put_pixel(0, 0, Rgba([0, 0, 0, 255]));
+
+    let mut framebuffer = Framebuffer::new(4, 1);
+    framebuffer.set_background_color(0x123456);
+    framebuffer.clear();
+    framebuffer.blit(&image, 0, 0, 4, 1, Filter::Bilinear);
+    assert_eq!(framebuffer.buffer, vec![0x000000, 0x404040, 0xBFBFBF, WHITE]);
+}
+
+#[test]
+fn blit_blends_with_the_image_alpha() {
+    let mut image = RgbaImage::new(2, 1);
+    image.put_pixel(0, 0, Rgba([255, 255, 255, 0]));
+    image.put_pixel(1, 0, Rgba([255, 0, 0, 128]));
+
+    let mut framebuffer = Framebuffer::new(2, 1);
+    framebuffer.set_background_color(0x0000FF);
+    framebuffer.clear();
+    framebuffer.blit(&image, 0, 0, 2, 1, Filter::Nearest);
+    assert_eq!(framebuffer.buffer, vec![0x0000FF, 0x80007F]);
+
+    // Con bilineal el color transparente no se mezcla en el borde
+    let mut framebuffer = Framebuffer::new(4, 1);
+    framebuffer.blit(&image, 0, 0, 4, 1, Filter::Bilinear);
+    assert_eq!(framebuffer.buffer[3] & 0x00FFFF, 0);
+}
+
+#[test]
+fn blit_is_clipped_to_the_buffer() {
+    let mut framebuffer = Framebuffer::new(2, 2);
+    framebuffer.blit(&checker(), -1, -1, 2, 2, Filter::Nearest);
+    assert_eq!(framebuffer.buffer, vec![WHITE, 0, 0, 0]);
+
+    framebuffer.blit(&checker(), 1, 1, 8, 8, Filter::Nearest);
+    assert_eq!(pixel(&framebuffer, 1, 1), 0xFF0000);
+}
+
+#[test]
+fn blit_bilinear_averages_every_pixel_when_shrinking() {
+    let mut image = RgbaImage::from_pixel(4, 1, Rgba([0, 0, 0, 255]));
+    image.put_pixel(0, 0, Rgba([255, 255, 255, 255]));
+
+    // Cuatro vecinos alrededor del centro no verían el píxel blanco
+    let mut framebuffer = Framebuffer::new(1, 1);
+    framebuffer.blit(&image, 0, 0, 1, 1, Filter::Bilinear);
+    assert_eq!(framebuffer.buffer, vec![0x404040]);
+}
+
+#[test]
+fn draw_image_scales_by_fractions() {
+    let image = RgbaImage::from_pixel(20, 20, Rgba([255, 255, 255, 255]));
+    for (scale, size) in [(0.1, 2), (0.5, 10), (1.0, 20), (1.5, 30), (1.25, 25)] {
+        let mut framebuffer = Framebuffer::new(40, 40);
+        draw_image(&mut framebuffer, &image, 0, 0, scale, (0.0, 0.0));
+        assert_eq!(painted(&framebuffer).len(), size * size, "escala {}", scale);
+    }
+
+    let mut framebuffer = Framebuffer::new(40, 40);
+    draw_image(&mut framebuffer, &image, 0, 0, 0.01, (0.0, 0.0));
+    assert!(painted(&framebuffer).is_empty());
+}
+
+#[test]
+fn draw_image_centres_on_the_anchor() {
+    let image = RgbaImage::from_pixel(2, 2, Rgba([255, 255, 255, 255]));
+    let mut framebuffer = Framebuffer::new(10, 10);
+    draw_image(&mut framebuffer, &image, 5, 5, 2.0, (0.5, 0.5));
+    let points = painted(&framebuffer);
+    assert_eq!(points.first(), Some(&(3, 3)));
+    assert_eq!(points.last(), Some(&(6, 6)));
+    assert_eq!(points.len(), 16);
+
+    // Más grande que el framebuffer: se recorta sin salirse
+    let mut framebuffer = Framebuffer::new(10, 10);
+    draw_image(&mut framebuffer, &image, 5, 5, 40.0, (0.5, 0.5));
+    assert_eq!(painted(&framebuffer).len(), 100);
+}
@@ -0,0 +1,83 @@
+use raycasting::generator::{generate, Algorithm};
+
+// Salida fija de cada algoritmo para 5 x 4 celdas y la semilla 2024. Si cambia, las
+// semillas compartidas y los retos diarios dejan de dar el mismo laberinto.
+const GOLDEN: [(Algorithm, &str); 5] = [
+    (Algorithm::RecursiveBacktracker, "\
++--+--+--+--+--+
+|>    |        |
++  +--+  +--+--+
+|  |           |
++  +--+--+--+  +
+|        |     |
++--+--+  +  +  +
+|           | g|
++--+--+--+--+--+
+"),
+    (Algorithm::Prim, "\
++--+--+--+--+--+
+|>             |
++  +  +--+  +  +
+|  |  |     |  |
++  +  +--+--+  +
+|  |     |     |
++--+  +  +  +  +
+|     |  |  | g|
++--+--+--+--+--+
+"),
+    (Algorithm::Kruskal, "\
++--+--+--+--+--+
+|>    |        |
++--+  +--+--+  +
+|     |  |  |  |
++  +--+  +  +  +
+|     |        |
++  +--+  +--+  +
+|        |    g|
++--+--+--+--+--+
+"),
+    (Algorithm::Eller, "\
++--+--+--+--+--+
+|>    |        |
++  +  +--+  +  +
+|  |        |  |
++  +  +  +  +  +
+|  |  |  |  |  |
++  +  +  +  +  +
+|  |  |  |  | g|
++--+--+--+--+--+
+"),
+    (Algorithm::Wilson, "\
++--+--+--+--+--+
+|>    |     |  |
++--+  +--+  +  +
+|  |  |        |
++  +  +--+  +  +
+|           |  |
++--+  +  +  +  +
+|     |  |  | g|
++--+--+--+--+--+
+"),
+];
+
+// Dibujo del laberinto sin el encabezado, que incluye la semilla
+fn drawing(algorithm: Algorithm, width: usize, height: usize, seed: u64) -> String {
+    let text = generate(algorithm, width, height, seed).to_text();
+    text.split_once("---\n").unwrap().1.to_string()
+}
+
+#[test]
+fn same_seed_gives_the_same_maze() {
+    for algorithm in Algorithm::ALL {
+        assert_eq!(generate(algorithm, 12, 9, 42).to_text(), generate(algorithm, 12, 9, 42).to_text(), "{}", algorithm.name());
+        assert_ne!(drawing(algorithm, 12, 9, 1), drawing(algorithm, 12, 9, 2), "{} da lo mismo con semillas distintas", algorithm.name());
+    }
+}
+
+#[test]
+fn output_matches_the_golden_mazes() {
+    for (algorithm, golden) in GOLDEN {
+        assert_eq!(drawing(algorithm, 5, 4, 2024), golden, "{}", algorithm.name());
+    }
+    assert_eq!(GOLDEN.map(|(algorithm, _)| algorithm), Algorithm::ALL);
+}
@@ -0,0 +1,129 @@
+use std::cell::Cell;
+
+use minifb::Key;
+use raycasting::backend::Backend;
+use raycasting::framebuffer::Framebuffer;
+use raycasting::input::{key_from_name, Action, Bindings, MouseLook, DEFAULT_MOUSE_SENSITIVITY};
+
+// Solo el mouse; `warps` dice si puede devolver el cursor al centro
+struct FakeMouse {
+    pos: Cell<(f32, f32)>,
+    warps: bool,
+}
+
+impl Backend for FakeMouse {
+    fn is_open(&self) -> bool {
+        true
+    }
+
+    fn is_key_down(&self, _key: Key) -> bool {
+        false
+    }
+
+    fn get_mouse_pos(&self) -> Option<(f32, f32)> {
+        Some(self.pos.get())
+    }
+
+    fn is_mouse_down(&self) -> bool {
+        false
+    }
+
+    fn set_cursor_visibility(&mut self, _visible: bool) {}
+
+    fn warp_mouse(&self, x: f32, y: f32) -> bool {
+        if self.warps {
+            self.pos.set((x, y));
+        }
+        self.warps
+    }
+
+    fn update(&mut self, _framebuffer: &Framebuffer) {}
+}
+
+#[test]
+fn index_follows_all() {
+    for (index, action) in Action::ALL.into_iter().enumerate() {
+        assert_eq!(action.index(), index);
+        assert_eq!(Action::from_name(action.name()), Some(action));
+    }
+}
+
+#[test]
+fn key_names_ignore_case() {
+    assert_eq!(key_from_name("w"), Some(Key::W));
+    assert_eq!(key_from_name("Key1"), Some(Key::Key1));
+    assert_eq!(key_from_name("leftshift"), Some(Key::LeftShift));
+    assert_eq!(key_from_name("NumPad5"), None);
+}
+
+#[test]
+fn bindings_round_trip() {
+    let (parsed, problems) = Bindings::parse(&Bindings::default().to_text());
+    assert!(problems.is_empty(), "{:?}", problems);
+    assert_eq!(parsed, Bindings::default());
+
+    let mut bindings = Bindings::default();
+    bindings.bind(Action::Hint, Key::Space);
+    bindings.bind(Action::Pause, Key::W);
+    bindings.clear(Action::ToggleMap);
+    let (parsed, problems) = Bindings::parse(&bindings.to_text());
+    assert!(problems.is_empty(), "{:?}", problems);
+    assert_eq!(parsed, bindings);
+}
+
+#[test]
+fn bind_takes_the_key_from_other_actions() {
+    let mut bindings = Bindings::default();
+    bindings.bind(Action::Hint, Key::W);
+    assert_eq!(bindings.keys(Action::MoveForward), &[Key::Up]);
+    assert_eq!(bindings.keys(Action::Hint), &[Key::H, Key::W]);
+}
+
+#[test]
+fn missing_actions_keep_their_defaults() {
+    let (bindings, problems) = Bindings::parse("# solo la pista\nhint: J\n\n");
+    assert!(problems.is_empty());
+    assert_eq!(bindings.keys(Action::Hint), &[Key::J]);
+    assert_eq!(bindings.keys(Action::MoveForward), Bindings::default().keys(Action::MoveForward));
+}
+
+#[test]
+fn invalid_lines_are_reported_and_skipped() {
+    let text = "move_forward W\njump: Space\nhint: J, Nada, K\n";
+    let (bindings, problems) = Bindings::parse(text);
+    assert_eq!(problems.len(), 3, "{:?}", problems);
+    assert!(problems[0].starts_with("línea 1:"));
+    assert!(problems[1].starts_with("línea 2:") && problems[1].contains("jump"));
+    assert!(problems[2].starts_with("línea 3:") && problems[2].contains("Nada"));
+
+    // Las teclas válidas de la línea se conservan
+    assert_eq!(bindings.keys(Action::Hint), &[Key::J, Key::K]);
+    assert_eq!(bindings.keys(Action::MoveForward), Bindings::default().keys(Action::MoveForward));
+}
+
+#[test]
+fn mouse_is_recentred_when_the_backend_can() {
+    let window = FakeMouse { pos: Cell::new((10.0, 50.0)), warps: true };
+    let mut mouse = MouseLook::new(100);
+
+    // La primera lectura no gira pero deja el cursor en el centro
+    assert_eq!(mouse.turn(&window, 0.1), 0.0);
+    assert_eq!(window.pos.get(), (50.0, 50.0));
+
+    // Pegado al borde no hay giro extra: solo cuenta lo que se movió
+    window.pos.set((0.0, 50.0));
+    let turn = mouse.turn(&window, 0.1);
+    assert!((turn + 50.0 * DEFAULT_MOUSE_SENSITIVITY).abs() < 1e-6, "{}", turn);
+    assert_eq!(window.pos.get(), (50.0, 50.0));
+}
+
+#[test]
+fn mouse_turns_at_the_edge_without_recentring() {
+    let window = FakeMouse { pos: Cell::new((0.0, 50.0)), warps: false };
+    let mut mouse = MouseLook::new(100);
+    mouse.turn(&window, 0.1);
+
+    // Sin moverse, en el borde izquierdo se sigue girando a la izquierda
+    assert!(mouse.turn(&window, 0.1) < 0.0);
+    assert_eq!(window.pos.get(), (0.0, 50.0));
+}
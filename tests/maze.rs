@@ -0,0 +1,116 @@
+use std::f32::consts::PI;
+use raycasting::maze::{load_maze, Maze, MazeError, DEFAULT_FACING};
+
+const BODY: &str = "+---+\n|S g|\n+---+\n";
+
+fn error(text: &str) -> MazeError {
+    match Maze::parse(text) {
+        Ok(_) => panic!("se esperaba un error para {:?}", text),
+        Err(err) => err,
+    }
+}
+
+#[test]
+fn parses_header_spawn_and_goal() {
+    let maze = Maze::parse(&format!("title: Prueba\nhints: 1\n# comentario\n---\n{}", BODY)).unwrap();
+    assert_eq!(maze.meta.title.as_deref(), Some("Prueba"));
+    assert_eq!(maze.meta.hints, 1);
+    assert_eq!((maze.width, maze.height), (5, 3));
+    assert_eq!(maze.spawn, (1, 1));
+    assert_eq!(maze.spawn_angle, DEFAULT_FACING);
+    assert_eq!(maze.goal, (3, 1));
+
+    // El marcador de aparición queda como celda libre
+    assert_eq!(maze.cell(1, 1), Some(' '));
+}
+
+#[test]
+fn arrows_set_the_facing() {
+    for (arrow, angle) in [('>', 0.0), ('v', PI / 2.0), ('<', PI), ('^', 3.0 * PI / 2.0)] {
+        let maze = Maze::parse(&BODY.replace('S', &arrow.to_string())).unwrap();
+        assert_eq!(maze.spawn_angle, angle, "{}", arrow);
+    }
+}
+
+#[test]
+fn missing_file_is_an_io_error() {
+    let Err(err) = load_maze("mazes/no_existe.txt") else { panic!("el archivo no existe") };
+    assert!(matches!(err, MazeError::Io { ref path, .. } if path == "mazes/no_existe.txt"));
+}
+
+#[test]
+fn bad_header_lines_are_rejected() {
+    let err = error(&format!("title Prueba\n---\n{}", BODY));
+    assert!(matches!(err, MazeError::InvalidHeader { line: 1, .. }), "{}", err);
+
+    let err = error(&format!("title: Prueba\ncolor: #FFFFFF\n---\n{}", BODY));
+    assert!(matches!(err, MazeError::InvalidHeader { line: 2, .. }), "{}", err);
+
+    let err = error(&format!("sky: azul\n---\n{}", BODY));
+    assert!(matches!(err, MazeError::InvalidHeader { line: 1, .. }), "{}", err);
+}
+
+#[test]
+fn header_without_separator_is_read_as_maze() {
+    let err = error(&format!("title: Prueba\n{}", BODY));
+    assert!(matches!(err, MazeError::UnknownCell { line: 1, column: 1, found: 't' }), "{}", err);
+}
+
+#[test]
+fn empty_maze_is_rejected() {
+    assert!(matches!(error(""), MazeError::Empty));
+    assert!(matches!(error("title: Prueba\n---\n\n\n"), MazeError::Empty));
+}
+
+#[test]
+fn ragged_rows_are_rejected() {
+    let err = error("+---+\n|S g |\n+---+\n");
+    assert!(matches!(err, MazeError::RaggedRow { line: 2, expected: 5, found: 6 }), "{}", err);
+}
+
+#[test]
+fn unknown_cells_are_rejected() {
+    let err = error("+---+\n|SXg|\n+---+\n");
+    assert!(matches!(err, MazeError::UnknownCell { line: 2, column: 3, found: 'X' }), "{}", err);
+}
+
+#[test]
+fn open_border_is_rejected() {
+    let err = error("+- -+\n|S g|\n+---+\n");
+    assert!(matches!(err, MazeError::UnenclosedBorder { line: 1, column: 3 }), "{}", err);
+}
+
+#[test]
+fn goal_is_required_and_unique() {
+    assert!(matches!(error("+---+\n|S  |\n+---+\n"), MazeError::MissingGoal));
+
+    let err = error("+----+\n|Sg g|\n+----+\n");
+    assert!(matches!(err, MazeError::DuplicateGoal { line: 2, column: 5 }), "{}", err);
+}
+
+#[test]
+fn spawn_is_required_and_unique() {
+    assert!(matches!(error("+---+\n|  g|\n+---+\n"), MazeError::MissingSpawn));
+
+    let err = error("+---+\n|S>g|\n+---+\n");
+    assert!(matches!(err, MazeError::DuplicateSpawn { line: 2, column: 3 }), "{}", err);
+}
+
+#[test]
+fn spawn_inside_walls_is_rejected() {
+    // En el borde exterior
+    let err = error("+-S-+\n|  g|\n+---+\n");
+    assert!(matches!(err, MazeError::SpawnInWall { line: 1, column: 3 }), "{}", err);
+
+    // Rodeado de paredes por los cuatro lados
+    let err = error("+---+\n|-+-|\n|+S+|\n|-+g|\n+---+\n");
+    assert!(matches!(err, MazeError::SpawnInWall { line: 3, column: 3 }), "{}", err);
+}
+
+#[test]
+fn missing_texture_names_the_key() {
+    let path = std::env::temp_dir().join("maze_missing_texture.txt");
+    std::fs::write(&path, format!("goal_texture: src/assets/images/no_existe.png\n---\n{}", BODY)).unwrap();
+    let Err(err) = load_maze(path.to_str().unwrap()) else { panic!("la textura no existe") };
+    assert!(matches!(err, MazeError::Texture { key: "goal_texture", .. }), "{}", err);
+}
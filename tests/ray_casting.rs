@@ -0,0 +1,41 @@
+use std::f32::consts::PI;
+
+use nalgebra_glm::Vec2;
+use raycasting::framebuffer::Framebuffer;
+use raycasting::maze::Maze;
+use raycasting::player::Player;
+use raycasting::ray_casting::cast_ray_minimap;
+
+// Celdas de 100 unidades: la pared izquierda termina en x = 100
+const ROOM: &str = "+-----+\n|S    |\n|  +  |\n|    g|\n+-----+\n";
+
+// Columnas pintadas en la fila `y`
+fn painted_row(framebuffer: &Framebuffer, y: usize) -> Vec<usize> {
+    (0..framebuffer.width).filter(|&x| framebuffer.get_pixel_color(x, y) != 0).collect()
+}
+
+#[test]
+fn minimap_ray_stops_at_the_wall_at_any_scale() {
+    let maze = Maze::parse(ROOM).unwrap();
+    let mut player = Player::at_spawn(&maze);
+    player.pos = Vec2::new(130.0, 150.0);
+
+    // El rayo hacia la izquierda llega a x = 99, dentro de la pared
+    for (scale, wall, start) in [(1.0, 99, 130), (0.5, 49, 65), (0.1, 9, 13)] {
+        let mut framebuffer = Framebuffer::new(200, 200);
+        cast_ray_minimap(&mut framebuffer, &maze, &player, PI, 0, 0, scale);
+        let y = (150.0 * scale) as usize;
+        assert_eq!(painted_row(&framebuffer, y), (wall..=start).collect::<Vec<_>>(), "escala {}", scale);
+    }
+}
+
+#[test]
+fn minimap_ray_is_capped_in_open_space() {
+    let maze = Maze::parse(ROOM).unwrap();
+    let mut player = Player::at_spawn(&maze);
+    player.pos = Vec2::new(130.0, 150.0);
+
+    let mut framebuffer = Framebuffer::new(200, 200);
+    cast_ray_minimap(&mut framebuffer, &maze, &player, 0.0, 0, 0, 0.5);
+    assert_eq!(painted_row(&framebuffer, 75), (65..=89).collect::<Vec<_>>());
+}
@@ -0,0 +1,32 @@
+use raycasting::player::InputFrame;
+use raycasting::replay::Recording;
+
+fn temp_path(name: &str) -> String {
+    std::env::temp_dir().join(name).to_string_lossy().into_owned()
+}
+
+#[test]
+fn recordings_round_trip() {
+    let mut recording = Recording::new("prim:3");
+    recording.frames.push(InputFrame { actions: 0b101, look: -0.25, dt: 1.0 / 60.0 });
+    recording.frames.push(InputFrame { actions: 0, look: 0.5, dt: 1.0 / 60.0 });
+
+    let path = temp_path("replay_round_trip.bin");
+    recording.save(&path).unwrap();
+    let loaded = Recording::load(&path).unwrap();
+    assert_eq!(loaded.level, "prim:3");
+    assert_eq!(loaded.frames, recording.frames);
+}
+
+#[test]
+fn older_formats_are_rejected() {
+    // BRATREC2 guardaba el giro en píxeles del mouse
+    let mut bytes = b"BRATREC2".to_vec();
+    bytes.extend_from_slice(&6u32.to_le_bytes());
+    bytes.extend_from_slice(b"prim:3");
+    bytes.extend_from_slice(&[0; 10]);
+
+    let path = temp_path("replay_old_format.bin");
+    std::fs::write(&path, bytes).unwrap();
+    assert!(Recording::load(&path).is_err());
+}
@@ -0,0 +1,57 @@
+use raycasting::generator::Algorithm;
+use raycasting::levels::Level;
+use raycasting::player::{InputFrame, TICK};
+use raycasting::replay::Recording;
+use raycasting::session::{Outcome, Session};
+
+fn new_session() -> Session {
+    Session::new(Level::generated(Algorithm::Prim, 6, 5, 3), None).unwrap()
+}
+
+#[test]
+fn steps_are_only_kept_when_recording() {
+    let mut session = new_session();
+    session.update(InputFrame::default(), 10.0 * TICK);
+    assert!(session.recording.is_none());
+
+    let mut session = new_session();
+    session.start_recording();
+    for _ in 0..10 {
+        session.update(InputFrame::default(), TICK * 1.01);
+    }
+    assert_eq!(session.recording.as_ref().map(|recording| recording.frames.len()), Some(10));
+}
+
+#[test]
+fn look_from_frames_without_a_step_is_not_lost() {
+    let mut session = new_session();
+    let start = session.player.a;
+    let look = InputFrame { look: 0.1, ..InputFrame::default() };
+
+    // Cuatro cuadros a 240 FPS dan un solo paso
+    for _ in 0..4 {
+        session.update(look, TICK / 4.0 + 1e-5);
+    }
+    assert!((session.player.a - start - 0.4).abs() < 1e-4, "{}", session.player.a - start);
+}
+
+#[test]
+fn replay_ends_when_frames_run_out_mid_update() {
+    let mut recording = Recording::new("prim:3");
+    recording.frames = vec![InputFrame { dt: TICK, ..InputFrame::default() }; 5];
+    let mut session = Session::new(Level::generated(Algorithm::Prim, 6, 5, 3), Some(recording)).unwrap();
+
+    // A 30 FPS cada cuadro pide dos pasos: el tercero se queda sin grabación a la mitad
+    let outcomes: Vec<Option<Outcome>> = (0..3).map(|_| session.update(InputFrame::default(), 2.0 * TICK)).collect();
+    assert_eq!(outcomes, vec![None, None, Some(Outcome::ReplayEnded)]);
+}
+
+#[test]
+fn restarting_the_random_maze_picks_a_new_seed() {
+    let session = Session::new(Level::random(), None).unwrap();
+    assert_ne!(session.restart().unwrap().path, session.path);
+
+    // Cualquier otro nivel se repite igual
+    let session = new_session();
+    assert_eq!(session.restart().unwrap().path, session.path);
+}
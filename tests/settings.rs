@@ -0,0 +1,53 @@
+use raycasting::settings::Settings;
+
+#[test]
+fn settings_round_trip() {
+    let (parsed, problems) = Settings::parse(&Settings::default().to_text());
+    assert!(problems.is_empty(), "{:?}", problems);
+    assert_eq!(parsed, Settings::default());
+
+    let settings = Settings {
+        width: 1600,
+        height: 900,
+        fov: 72.5,
+        mouse_sensitivity: 0.0125,
+        invert_mouse: true,
+        mouse_smoothing: 0.3,
+        music_volume: 0.25,
+        effects_volume: 0.0,
+        minimap_scale: 0.35,
+        textures: false,
+        fog: false,
+        show_fps: false,
+    };
+    let (parsed, problems) = Settings::parse(&settings.to_text());
+    assert!(problems.is_empty(), "{:?}", problems);
+    assert_eq!(parsed, settings);
+}
+
+#[test]
+fn out_of_range_values_keep_the_default() {
+    let (settings, problems) = Settings::parse("fov: 170\nmusic_volume: -0.5\nresolution: 100x100\nminimap_scale: 0.3\n");
+    assert_eq!(problems.len(), 3, "{:?}", problems);
+    assert!(problems[0].starts_with("línea 1:") && problems[0].contains("fov"));
+    assert_eq!(settings.fov, Settings::default().fov);
+    assert_eq!(settings.music_volume, Settings::default().music_volume);
+    assert_eq!((settings.width, settings.height), (Settings::default().width, Settings::default().height));
+    assert_eq!(settings.minimap_scale, 0.3);
+}
+
+#[test]
+fn unknown_keys_are_reported() {
+    let (settings, problems) = Settings::parse("# comentario\ngamma: 2\nfog: off\n");
+    assert_eq!(problems.len(), 1);
+    assert!(problems[0].starts_with("línea 2:") && problems[0].contains("gamma"));
+    assert!(!settings.fog);
+}
+
+#[test]
+fn lines_without_colon_are_reported() {
+    let (settings, problems) = Settings::parse("fov 90\n");
+    assert_eq!(problems.len(), 1);
+    assert!(problems[0].starts_with("línea 1:"));
+    assert_eq!(settings, Settings::default());
+}
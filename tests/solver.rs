@@ -0,0 +1,75 @@
+use raycasting::maze::Maze;
+use raycasting::solver::{analyze, CellGraph};
+
+// 3 x 2 celdas en forma de S: un solo camino, sin cruces
+const SNAKE: &str = "\
++--+--+--+
+|v       |
++--+--+  +
+|g       |
++--+--+--+
+";
+
+// 3 x 2 celdas con dos cruces en la columna del medio
+const CROSS: &str = "\
++--+--+--+
+|v       |
++--+  +--+
+|       g|
++--+--+--+
+";
+
+#[test]
+fn lattice_mazes_are_measured_in_cells() {
+    let maze = Maze::parse(SNAKE).unwrap();
+    let graph = CellGraph::new(&maze);
+    assert_eq!((graph.width, graph.height), (3, 2));
+    assert_eq!(graph.cell_at(maze.spawn), (0, 0));
+    assert_eq!(graph.cell_at(maze.goal), (0, 1));
+}
+
+#[test]
+fn snake_counts() {
+    let analysis = analyze(&Maze::parse(SNAKE).unwrap());
+    assert!(analysis.solvable);
+    assert_eq!(analysis.path, vec![(0, 0), (1, 0), (2, 0), (2, 1), (1, 1), (0, 1)]);
+    assert_eq!(analysis.path_length(), 5);
+    assert_eq!(analysis.turns, 2);
+    assert_eq!((analysis.open_cells, analysis.reachable_cells), (6, 6));
+    assert_eq!((analysis.dead_ends, analysis.junctions), (2, 0));
+}
+
+#[test]
+fn cross_counts() {
+    let analysis = analyze(&Maze::parse(CROSS).unwrap());
+    assert_eq!(analysis.path, vec![(0, 0), (1, 0), (1, 1), (2, 1)]);
+    assert_eq!(analysis.turns, 2);
+    assert_eq!((analysis.open_cells, analysis.reachable_cells), (6, 6));
+    assert_eq!((analysis.dead_ends, analysis.junctions), (4, 2));
+}
+
+#[test]
+fn walled_off_goal_is_unsolvable() {
+    let maze = Maze::parse("+--+--+\n|v |  |\n+--+--+\n|  | g|\n+--+--+\n").unwrap();
+    let analysis = analyze(&maze);
+    assert!(!analysis.solvable);
+    assert_eq!((analysis.open_cells, analysis.reachable_cells), (4, 1));
+}
+
+#[test]
+fn bundled_level_has_sixteen_cells() {
+    let analysis = analyze(&raycasting::maze::load_maze("mazes/maze1.txt").unwrap());
+    assert!(analysis.solvable);
+    assert_eq!((analysis.open_cells, analysis.reachable_cells), (16, 16));
+}
+
+#[test]
+fn free_form_mazes_are_measured_per_character() {
+    let maze = Maze::parse("+-----+\n|S    |\n|  +  |\n|    g|\n+-----+\n").unwrap();
+    let graph = CellGraph::new(&maze);
+    assert_eq!((graph.width, graph.height), (7, 5));
+
+    let analysis = analyze(&maze);
+    assert!(analysis.solvable);
+    assert_eq!(analysis.open_cells, 13);
+}
@@ -0,0 +1,76 @@
+use raycasting::framebuffer::Framebuffer;
+use raycasting::text::{Align, Bounds, Font, TextStyle, DEFAULT_FONT};
+
+fn font() -> Font {
+    Font::load(DEFAULT_FONT).unwrap()
+}
+
+fn style(align: Align, max_width: Option<usize>) -> TextStyle {
+    TextStyle { size: 20.0, color: 0xFFFFFF, align, max_width }
+}
+
+// Columnas pintadas de más a la izquierda y más a la derecha
+fn painted_columns(framebuffer: &Framebuffer) -> (i32, i32) {
+    let columns: Vec<i32> = framebuffer.buffer.iter().enumerate()
+        .filter(|(_, &pixel)| pixel != 0)
+        .map(|(index, _)| (index % framebuffer.width) as i32)
+        .collect();
+    (*columns.iter().min().unwrap(), *columns.iter().max().unwrap())
+}
+
+#[test]
+fn wraps_between_words() {
+    let mut font = font();
+    let line = font.measure("A", 0, 0, &style(Align::Left, None)).height;
+    let first = font.measure("uno dos", 0, 0, &style(Align::Left, None));
+
+    let wrapped = font.measure("uno dos tres", 0, 0, &style(Align::Left, Some(first.width)));
+    assert_eq!(wrapped, Bounds { x: 0, y: 0, width: first.width, height: 2 * line });
+
+    // Sin ancho máximo solo parten los saltos de línea
+    let unwrapped = font.measure("uno dos tres", 0, 0, &style(Align::Left, None));
+    assert_eq!(unwrapped.height, line);
+    assert_eq!(font.measure("uno\ndos", 0, 0, &style(Align::Left, None)).height, 2 * line);
+}
+
+#[test]
+fn long_words_are_split_between_letters() {
+    let mut font = font();
+    let line = font.measure("A", 0, 0, &style(Align::Left, None)).height;
+    let max_width = font.measure("mmm", 0, 0, &style(Align::Left, None)).width;
+
+    let bounds = font.measure("mmmmmmmmm", 0, 0, &style(Align::Left, Some(max_width)));
+    assert_eq!(bounds.height, 3 * line);
+    assert!(bounds.width <= max_width);
+}
+
+#[test]
+fn alignment_moves_the_bounds() {
+    let mut font = font();
+    let left = font.measure("Hola", 100, 10, &style(Align::Left, None));
+    assert_eq!((left.x, left.y), (100, 10));
+
+    let center = font.measure("Hola", 100, 10, &style(Align::Center, None));
+    assert_eq!(center.x, 100 - left.width as i32 / 2);
+    assert_eq!((center.width, center.height), (left.width, left.height));
+
+    let right = font.measure("Hola", 100, 10, &style(Align::Right, None));
+    assert_eq!(right.x, 100 - left.width as i32);
+}
+
+#[test]
+fn drawn_text_stays_inside_its_bounds() {
+    let mut font = font();
+    for align in [Align::Center, Align::Right] {
+        let mut framebuffer = Framebuffer::new(200, 40);
+        let bounds = font.draw(&mut framebuffer, "Hola", 100, 5, &style(align, None));
+        let (first, last) = painted_columns(&framebuffer);
+        assert!(first >= bounds.x && last < bounds.x + bounds.width as i32, "{:?}: {}..{} fuera de {:?}", align, first, last, bounds);
+    }
+
+    // Centrado, queda el mismo espacio a cada lado de x
+    let mut framebuffer = Framebuffer::new(200, 40);
+    font.draw(&mut framebuffer, "HH", 100, 5, &style(Align::Center, None));
+    let (first, last) = painted_columns(&framebuffer);
+    assert!(((100 - first) - (last + 1 - 100)).abs() <= 2, "{}..{}", first, last);
+}
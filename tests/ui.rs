@@ -0,0 +1,163 @@
+use std::cell::RefCell;
+
+use minifb::Key;
+use raycasting::backend::Backend;
+use raycasting::framebuffer::Framebuffer;
+use raycasting::ui::{Rect, Ui};
+
+// Solo teclado; `down` son las teclas presionadas en este momento
+#[derive(Default)]
+struct FakeKeys {
+    down: RefCell<Vec<Key>>,
+}
+
+impl Backend for FakeKeys {
+    fn is_open(&self) -> bool {
+        true
+    }
+
+    fn is_key_down(&self, key: Key) -> bool {
+        self.down.borrow().contains(&key)
+    }
+
+    fn get_mouse_pos(&self) -> Option<(f32, f32)> {
+        None
+    }
+
+    fn is_mouse_down(&self) -> bool {
+        false
+    }
+
+    fn set_cursor_visibility(&mut self, _visible: bool) {}
+
+    fn warp_mouse(&self, _x: f32, _y: f32) -> bool {
+        false
+    }
+
+    fn update(&mut self, _framebuffer: &Framebuffer) {}
+}
+
+// Pantalla de prueba: tres botones, uno debajo del otro
+struct Screen {
+    ui: Ui,
+    window: FakeKeys,
+    framebuffer: Framebuffer,
+}
+
+impl Screen {
+    fn new() -> Self {
+        let mut screen = Screen { ui: Ui::default(), window: FakeKeys::default(), framebuffer: Framebuffer::new(400, 400) };
+        // Al abrir, todo cuenta como presionado hasta el primer cuadro sin teclas
+        screen.buttons(&[]);
+        screen
+    }
+
+    // Un cuadro con `keys` presionadas; devuelve los botones elegidos
+    fn buttons(&mut self, keys: &[Key]) -> Vec<usize> {
+        *self.window.down.borrow_mut() = keys.to_vec();
+        self.ui.begin(&self.window);
+        let chosen = (0..3)
+            .filter(|&index| self.ui.button(&mut self.framebuffer, Rect::new(0, index * 40, 200, 32), "Botón"))
+            .collect();
+        self.ui.end();
+        chosen
+    }
+
+    // Presiona y suelta una tecla
+    fn press(&mut self, key: Key) -> Vec<usize> {
+        let chosen = self.buttons(&[key]);
+        self.buttons(&[]);
+        chosen
+    }
+
+    // Presiona y suelta `keys` sobre una lista de `count` filas con páginas de `rows`
+    fn list(&mut self, keys: &[Key], count: usize, selected: &mut usize, rows: usize) -> Option<usize> {
+        let items: Vec<(String, u32)> = (0..count).map(|index| (format!("Nivel {}", index), 0xFFFFFF)).collect();
+        let mut chosen = None;
+        for down in [keys, &[]] {
+            *self.window.down.borrow_mut() = down.to_vec();
+            self.ui.begin(&self.window);
+            chosen = chosen.or(self.ui.list(&mut self.framebuffer, Rect::new(0, 0, 300, 200), &items, selected, rows));
+            self.ui.end();
+        }
+        chosen
+    }
+}
+
+#[test]
+fn arrows_move_the_focus_and_wrap() {
+    let mut screen = Screen::new();
+    assert_eq!(screen.ui.focus(), 0);
+
+    screen.press(Key::Down);
+    assert_eq!(screen.ui.focus(), 1);
+    screen.press(Key::S);
+    assert_eq!(screen.ui.focus(), 2);
+    screen.press(Key::Down);
+    assert_eq!(screen.ui.focus(), 0);
+    screen.press(Key::Up);
+    assert_eq!(screen.ui.focus(), 2);
+}
+
+#[test]
+fn only_the_focused_button_is_activated_once() {
+    let mut screen = Screen::new();
+    screen.press(Key::Down);
+
+    assert_eq!(screen.buttons(&[Key::Enter]), [1]);
+    // Mantener la tecla no vuelve a elegirlo
+    assert!(screen.buttons(&[Key::Enter]).is_empty());
+    assert!(screen.buttons(&[]).is_empty());
+    assert_eq!(screen.press(Key::Space), [1]);
+}
+
+#[test]
+fn keys_held_when_the_screen_opens_are_ignored() {
+    let mut screen = Screen::new();
+    screen.buttons(&[Key::Enter]);
+    screen.ui.reset();
+
+    assert!(screen.buttons(&[Key::Enter]).is_empty());
+    screen.buttons(&[]);
+    assert_eq!(screen.press(Key::Enter), [0]);
+}
+
+#[test]
+fn lists_page_and_wrap() {
+    let mut screen = Screen::new();
+    let mut selected = 0;
+    screen.list(&[], 10, &mut selected, 4);
+
+    screen.list(&[Key::PageDown], 10, &mut selected, 4);
+    assert_eq!(selected, 4);
+    screen.list(&[Key::Right], 10, &mut selected, 4);
+    assert_eq!(selected, 8);
+    // La última página está incompleta: se queda en la última fila
+    screen.list(&[Key::Right], 10, &mut selected, 4);
+    assert_eq!(selected, 9);
+    screen.list(&[Key::PageUp], 10, &mut selected, 4);
+    assert_eq!(selected, 5);
+
+    screen.list(&[Key::Down], 10, &mut selected, 4);
+    assert_eq!(selected, 6);
+    selected = 9;
+    screen.list(&[Key::Down], 10, &mut selected, 4);
+    assert_eq!(selected, 0);
+    screen.list(&[Key::Up], 10, &mut selected, 4);
+    assert_eq!(selected, 9);
+
+    assert_eq!(screen.list(&[Key::Enter], 10, &mut selected, 4), Some(9));
+}
+
+#[test]
+fn lists_without_rows_still_work() {
+    let mut screen = Screen::new();
+    let mut selected = 0;
+    screen.list(&[], 3, &mut selected, 0);
+    screen.list(&[Key::PageDown], 3, &mut selected, 0);
+    assert_eq!(selected, 1);
+    assert_eq!(screen.list(&[Key::Enter], 3, &mut selected, 0), Some(1));
+
+    // Una lista vacía no elige nada
+    assert_eq!(screen.list(&[Key::Enter], 0, &mut selected, 4), None);
+}